@@ -25,6 +25,13 @@ fn pcg32_fill_bytes(b: &mut Bencher) {
     b.iter(|| rng.fill_bytes(x.as_mut_slice()))
 }
 
+#[bench]
+fn pcg32_advance(b: &mut Bencher) {
+    let mut rng = Pcg32::from_entropy();
+
+    b.iter(|| rng.advance(0xDEAD_BEEF_0123_4567))
+}
+
 #[bench]
 fn pcg32fast_next_u32(b: &mut Bencher) {
     let mut rng = Pcg32Fast::from_entropy();
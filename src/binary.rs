@@ -0,0 +1,316 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! A compact binary format for saved generators which does not need serde.
+//!
+//! `PcgEngine::to_bytes` and `ExtPcg::to_bytes` write the following fields,
+//! every integer is little endian:
+//!
+//! | Field             | Size                          |
+//! |-------------------|-------------------------------|
+//! | `MAGIC`           | 4 bytes                       |
+//! | `VERSION`         | 2 bytes                       |
+//! | internal width    | 1 byte, in bits               |
+//! | output width      | 1 byte, in bits               |
+//! | output mixin id   | 1 byte length, then the bytes |
+//! | stream id         | 1 byte length, then the bytes |
+//! | multiplier        | internal width                |
+//! | state             | internal width                |
+//! | increment         | internal width                |
+//! | extension length  | 4 bytes, 0 for a `PcgEngine`  |
+//! | extension values  | output width each             |
+//! | checksum          | 8 bytes                       |
+//!
+//! The ids are the `SERIALIZER_ID`s of the output mixin and the stream, and
+//! the checksum is `StableHasher::finish` of all the bytes before it. Every
+//! generator of one type is saved with the same number of bytes, 56 for a
+//! `Pcg32`. `to_bytes` fails with `PcgError::FieldTooLarge` if a field does
+//! not fit in its bytes, like an id longer than 255 bytes.
+//!
+//! `from_bytes` checks the format and then does the same checks as
+//! `restore_state`.
+//!
+//! ```
+//! extern crate pcg_rand;
+//! extern crate rand;
+//!
+//! use pcg_rand::Pcg32;
+//! use rand::{RngCore, SeedableRng};
+//!
+//! let mut ra = Pcg32::from_entropy();
+//! let bytes = ra.to_bytes().unwrap();
+//! let mut rb = Pcg32::from_bytes(&bytes).unwrap();
+//! assert_eq!(ra.next_u32(), rb.next_u32());
+//! ```
+
+use core::convert::TryFrom;
+use core::hash::Hasher;
+use core::mem::size_of;
+use error::PcgError;
+use extension::{ExtPcg, ExtSize};
+use multiplier::Multiplier;
+use num_traits::{One, Zero};
+use numops::BitSize;
+use outputmix::OutputMixin;
+use seeds::{ReadByteOrder, StableHasher};
+use stream::{RestorableStream, Stream};
+use {PCGStateInfo, PcgEngine};
+
+/// The first bytes of every saved generator.
+pub const MAGIC: [u8; 4] = *b"PCGB";
+
+/// The version of the format written by this crate.
+pub const VERSION: u16 = 1;
+
+const CHECKSUM_BYTES: usize = size_of::<u64>();
+
+// Writes a recorded state and extension array in the binary format
+fn encode<Itype, Xtype>(state: &PCGStateInfo<Itype>, ext: &[Xtype]) -> Result<Vec<u8>, PcgError>
+where
+    Itype: ReadByteOrder,
+    Xtype: ReadByteOrder,
+{
+    let mut out = Vec::new();
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.push(fit("internal width", state.internal_width)?);
+    out.push(fit("output width", state.output_width)?);
+
+    for &(field, id) in [
+        ("output mixin id", &state.output_mixin),
+        ("stream id", &state.stream),
+    ]
+    .iter()
+    {
+        out.push(fit(field, id.len())?);
+        out.extend_from_slice(id.as_bytes());
+    }
+
+    for val in [&state.multiplier, &state.state, &state.increment].iter() {
+        write_value(&mut out, *val);
+    }
+
+    let ext_len: u32 = fit("extension length", ext.len())?;
+    out.extend_from_slice(&ext_len.to_le_bytes());
+    for val in ext {
+        write_value(&mut out, val);
+    }
+
+    let mut hasher = StableHasher::new();
+    hasher.write(&out);
+    out.extend_from_slice(&hasher.finish().to_le_bytes());
+    Ok(out)
+}
+
+// Checks that a width or length fits in the bytes the format gives it
+fn fit<T: TryFrom<usize>>(field: &'static str, value: usize) -> Result<T, PcgError> {
+    T::try_from(value).map_err(|_| PcgError::FieldTooLarge { field, value })
+}
+
+fn write_value<T: ReadByteOrder>(out: &mut Vec<u8>, val: &T) {
+    let start = out.len();
+    out.resize(start + size_of::<T>(), 0);
+    val.write(&mut out[start..]);
+}
+
+// Reads a recorded state and fills `ext` with the recorded extension array
+fn decode<Itype, Xtype>(bytes: &[u8], ext: &mut [Xtype]) -> Result<PCGStateInfo<Itype>, PcgError>
+where
+    Itype: ReadByteOrder + BitSize,
+    Xtype: ReadByteOrder + BitSize,
+{
+    if bytes.len() < MAGIC.len() || bytes[..MAGIC.len()] != MAGIC {
+        return Err(PcgError::BadMagic);
+    }
+
+    let header = MAGIC.len() + size_of::<u16>();
+    if bytes.len() < header + CHECKSUM_BYTES {
+        return Err(PcgError::LengthMismatch {
+            expected: header + CHECKSUM_BYTES,
+            found: bytes.len(),
+        });
+    }
+
+    let version = u16::read(&bytes[MAGIC.len()..]);
+    if version != VERSION {
+        return Err(PcgError::UnsupportedVersion { found: version });
+    }
+
+    let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_BYTES);
+    let mut hasher = StableHasher::new();
+    hasher.write(body);
+    if hasher.finish().to_le_bytes() != checksum {
+        return Err(PcgError::ChecksumMismatch);
+    }
+
+    let mut reader = Reader {
+        bytes: &body[header..],
+        read: header,
+    };
+
+    // The widths are checked first since they decide how the rest is read
+    let internal_width = usize::from(reader.byte()?);
+    let output_width = usize::from(reader.byte()?);
+    if internal_width != Itype::BITS || output_width != Xtype::BITS {
+        return Err(PcgError::WidthMismatch {
+            expected: (Itype::BITS, Xtype::BITS),
+            found: (internal_width, output_width),
+        });
+    }
+
+    let output_mixin = reader.id()?;
+    let stream = reader.id()?;
+    let multiplier = reader.value()?;
+    let state = reader.value()?;
+    let increment = reader.value()?;
+
+    let ext_len = u32::read(reader.take(size_of::<u32>())?) as usize;
+    if ext_len != ext.len() {
+        return Err(PcgError::ExtSizeMismatch {
+            expected: ext.len(),
+            found: ext_len,
+        });
+    }
+    for val in ext.iter_mut() {
+        *val = reader.value()?;
+    }
+
+    if !reader.bytes.is_empty() {
+        return Err(PcgError::LengthMismatch {
+            expected: reader.read + CHECKSUM_BYTES,
+            found: bytes.len(),
+        });
+    }
+
+    Ok(PCGStateInfo {
+        state,
+        increment,
+        multiplier,
+        internal_width,
+        output_width,
+        output_mixin,
+        stream,
+    })
+}
+
+// Reads fields from the front of a saved generator
+struct Reader<'a> {
+    bytes: &'a [u8],
+    read: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PcgError> {
+        if n > self.bytes.len() {
+            return Err(PcgError::LengthMismatch {
+                expected: self.read + n + CHECKSUM_BYTES,
+                found: self.read + self.bytes.len() + CHECKSUM_BYTES,
+            });
+        }
+
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        self.read += n;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, PcgError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn value<T: ReadByteOrder>(&mut self) -> Result<T, PcgError> {
+        Ok(T::read(self.take(size_of::<T>())?))
+    }
+
+    fn id(&mut self) -> Result<String, PcgError> {
+        let len = usize::from(self.byte()?);
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: Copy + BitSize + ReadByteOrder,
+    Xtype: BitSize + ReadByteOrder,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Saves the PCG in the binary format described in the `binary` module.
+    /// This fails if an id of the output mixin or stream is longer than 255
+    /// bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PcgError> {
+        encode::<Itype, Xtype>(&self.get_state(), &[])
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+    ExtPcg<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+where
+    Itype: Copy + BitSize + ReadByteOrder,
+    Xtype: BitSize + ReadByteOrder,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    Size: ExtSize,
+{
+    /// Saves the PCG and its extension array in the binary format described
+    /// in the `binary` module. This fails if an id of the output mixin or
+    /// stream is longer than 255 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PcgError> {
+        encode(&self.pcg.get_state(), self.ext.as_ref())
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype:
+        Copy + Eq + Zero + One + core::ops::BitAnd<Itype, Output = Itype> + BitSize + ReadByteOrder,
+    Xtype: BitSize + ReadByteOrder,
+    StreamMix: RestorableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Restores a PCG saved with `to_bytes` and verifies that it was
+    /// saved by the same kind of PCG.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PcgError> {
+        Self::restore_state(decode::<Itype, Xtype>(bytes, &mut [])?)
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+    ExtPcg<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+where
+    Itype:
+        Copy + Eq + Zero + One + core::ops::BitAnd<Itype, Output = Itype> + BitSize + ReadByteOrder,
+    Xtype: Zero + BitSize + ReadByteOrder,
+    StreamMix: RestorableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    Size: ExtSize,
+{
+    /// Restores a PCG saved with `to_bytes` and verifies that it was
+    /// saved by the same kind of PCG with the same extension size.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PcgError> {
+        let mut ext = Size::build_storage(Xtype::zero);
+        let state = decode(bytes, ext.as_mut())?;
+        let pcg = PcgEngine::<Itype, Xtype, StreamMix, MulMix, OutMix>::restore_state(state)?;
+        Ok(ExtPcg::from_parts(pcg, ext))
+    }
+}
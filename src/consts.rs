@@ -0,0 +1,231 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! Building and stepping generators in `const` code.
+//!
+//! Trait methods can not be called in `const` code, so these functions are
+//! generated for each of the built-in state types. Stepping needs a
+//! `ConstMultiplier` and a stream with an increment known in `const` code,
+//! producing an output needs one of the built-in output mixins.
+
+use core::marker::PhantomData;
+use multiplier::{ConstMultiplier, Multiplier};
+use outputmix::{DXsMMixin, OutputMixin, XshRrMixin, XshRsMixin};
+use stream::{ConstStream, SpecificSeqStream, Stream};
+use {Pcg32Basic, PcgEngine};
+
+macro_rules! make_const_engine {
+    ( $( $t:ident => $advance:ident );* ) => {
+        $(
+        // Advances the LCG state by `delta` steps in O(log(delta)) time
+        const fn $advance(state: $t, delta: $t, mult: $t, inc: $t) -> $t {
+            let mut acc_mult: $t = 1;
+            let mut acc_plus: $t = 0;
+            let mut cur_mult = mult;
+            let mut cur_plus = inc;
+            let mut delta = delta;
+
+            while delta > 0 {
+                if delta & 1 != 0 {
+                    acc_mult = acc_mult.wrapping_mul(cur_mult);
+                    acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+                }
+                cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+                cur_mult = cur_mult.wrapping_mul(cur_mult);
+                delta >>= 1;
+            }
+
+            acc_mult.wrapping_mul(state).wrapping_add(acc_plus)
+        }
+
+        impl<Xtype, MulMix, OutMix> PcgEngine<$t, Xtype, SpecificSeqStream<$t>, MulMix, OutMix>
+        where
+            MulMix: Multiplier<$t>,
+            OutMix: OutputMixin<$t, Xtype>,
+        {
+            /// Builds a PCG in `const` code. This gives the same PCG as
+            /// seeding it with `PcgSeeder::seed_with_stream(seed, stream)`.
+            ///
+            /// ```
+            /// extern crate pcg_rand;
+            ///
+            /// use pcg_rand::Pcg32;
+            ///
+            /// const fn make_table() -> [u32; 4] {
+            ///     let mut rng = Pcg32::const_new(42, 54);
+            ///     let mut table = [0; 4];
+            ///     let mut i = 0;
+            ///     while i < table.len() {
+            ///         table[i] = rng.const_output();
+            ///         rng = rng.const_step();
+            ///         i += 1;
+            ///     }
+            ///     table
+            /// }
+            ///
+            /// static TABLE: [u32; 4] = make_table();
+            /// ```
+            pub const fn const_new(seed: $t, stream: $t) -> Self {
+                PcgEngine {
+                    state: seed,
+                    origin: seed,
+                    stream_mix: SpecificSeqStream::<$t>::with_stream(stream),
+                    mul_mix: PhantomData,
+                    out_mix: PhantomData,
+                    phantom: PhantomData,
+                }
+            }
+
+            /// Takes one step in `const` code, like `next_u32` or `next_u64`
+            /// without producing the output.
+            pub const fn const_step(self) -> Self
+            where
+                MulMix: ConstMultiplier<$t>,
+            {
+                let mut pcg = self;
+                pcg.state = pcg
+                    .state
+                    .wrapping_mul(MulMix::MULTIPLIER)
+                    .wrapping_add(pcg.stream_mix.inc);
+                pcg
+            }
+
+            /// Advances the PCG by `delta` steps in `const` code.
+            pub const fn const_advance(self, delta: $t) -> Self
+            where
+                MulMix: ConstMultiplier<$t>,
+            {
+                let mut pcg = self;
+                pcg.state = $advance(pcg.state, delta, MulMix::MULTIPLIER, pcg.stream_mix.inc);
+                pcg
+            }
+        }
+
+        impl<Xtype, StreamMix, MulMix, OutMix> PcgEngine<$t, Xtype, StreamMix, MulMix, OutMix>
+        where
+            StreamMix: ConstStream<$t>,
+            MulMix: Multiplier<$t>,
+            OutMix: OutputMixin<$t, Xtype>,
+        {
+            /// Builds a PCG in `const` code. This gives the same PCG as
+            /// seeding it with `PcgSeeder::seed(seed)`.
+            pub const fn const_new(seed: $t) -> Self {
+                PcgEngine {
+                    state: seed,
+                    origin: seed,
+                    stream_mix: StreamMix::STREAM,
+                    mul_mix: PhantomData,
+                    out_mix: PhantomData,
+                    phantom: PhantomData,
+                }
+            }
+
+            /// Takes one step in `const` code, like `next_u32` or `next_u64`
+            /// without producing the output.
+            pub const fn const_step(self) -> Self
+            where
+                MulMix: ConstMultiplier<$t>,
+            {
+                let mut pcg = self;
+                pcg.state = pcg
+                    .state
+                    .wrapping_mul(MulMix::MULTIPLIER)
+                    .wrapping_add(StreamMix::INCREMENT);
+                pcg
+            }
+
+            /// Advances the PCG by `delta` steps in `const` code.
+            pub const fn const_advance(self, delta: $t) -> Self
+            where
+                MulMix: ConstMultiplier<$t>,
+            {
+                let mut pcg = self;
+                pcg.state = $advance(pcg.state, delta, MulMix::MULTIPLIER, StreamMix::INCREMENT);
+                pcg
+            }
+        })*
+    }
+}
+
+make_const_engine!(
+    u32 => advance_u32;
+    u64 => advance_u64;
+    u128 => advance_u128
+);
+
+macro_rules! make_const_output {
+    ( $( $i:ty, $x:ty, $mix:ident => $name:ident );* ) => {
+        $(
+        impl<StreamMix, MulMix> PcgEngine<$i, $x, StreamMix, MulMix, $mix>
+        where
+            StreamMix: Stream<$i>,
+            MulMix: ConstMultiplier<$i>,
+        {
+            /// Returns the output for the current state in `const` code, this
+            /// is the value the next call to `next_u32` or `next_u64` returns.
+            pub const fn const_output(&self) -> $x {
+                $mix::$name(self.state, MulMix::MULTIPLIER)
+            }
+        })*
+    }
+}
+
+make_const_output!(
+    u64, u32, XshRsMixin => output_u64_u32;
+    u64, u32, XshRrMixin => output_u64_u32;
+    u64, u32, DXsMMixin => output_u64_u32;
+    u128, u32, XshRsMixin => output_u128_u32;
+    u128, u32, XshRrMixin => output_u128_u32;
+    u128, u32, DXsMMixin => output_u128_u32;
+    u128, u64, XshRsMixin => output_u128_u64;
+    u128, u64, XshRrMixin => output_u128_u64;
+    u128, u64, DXsMMixin => output_u128_u64
+);
+
+impl Pcg32Basic {
+    /// Builds a PCG in `const` code. This gives the same PCG as seeding it
+    /// with `PcgSeeder::seed_with_stream(seed, stream)`.
+    pub const fn const_new(seed: u64, stream: u64) -> Pcg32Basic {
+        Pcg32Basic {
+            state: seed,
+            inc: stream,
+        }
+    }
+
+    /// Takes one step in `const` code, like `next_u32` without producing
+    /// the output.
+    pub const fn const_step(self) -> Pcg32Basic {
+        Pcg32Basic {
+            state: self
+                .state
+                .wrapping_mul(6_364_136_223_846_793_005u64)
+                .wrapping_add(self.inc | 1),
+            inc: self.inc,
+        }
+    }
+
+    /// Returns the output for the current state in `const` code, this is the
+    /// value the next call to `next_u32` returns.
+    pub const fn const_output(&self) -> u32 {
+        let oldstate = self.state;
+        let xorshifted: u32 = (((oldstate >> 18) ^ oldstate) >> 27) as u32;
+        let rot: u32 = (oldstate >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+}
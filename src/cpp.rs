@@ -0,0 +1,130 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+// The text format of `operator<<` and `operator>>` in the C++ library, the
+// multiplier, the increment and the state as decimal numbers separated by
+// spaces.
+//
+// The C++ engines with a state of 64 bits or less compute their output from
+// the state before stepping, like every engine in this crate. The larger
+// engines step first and then compute the output from the new state, so
+// their state is one step behind ours.
+
+use core::fmt;
+use error::PcgError;
+use lcg::LcgStep;
+use multiplier::Multiplier;
+use num_traits::{Num, One, WrappingSub, Zero};
+use numops::{BitSize, PcgOps};
+use outputmix::OutputMixin;
+use stream::{RestorableStream, Stream};
+use {PCGStateInfo, PcgEngine};
+
+// Whether the C++ engine with this state size steps before computing output
+fn cpp_steps_first<Itype: BitSize>() -> bool {
+    Itype::BITS > 64
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps
+        + Copy
+        + One
+        + Zero
+        + Ord
+        + Eq
+        + core::ops::BitAnd<Itype, Output = Itype>
+        + core::ops::ShrAssign
+        + WrappingSub
+        + BitSize
+        + fmt::Display,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Writes the PCG the way `operator<<` of the C++ library does, as
+    /// `multiplier increment state` in decimal. The C++ engine with the same
+    /// output function reads this with `operator>>` and continues with the
+    /// same outputs.
+    pub fn to_cpp_text(&self) -> String {
+        let mut state = self.state;
+        if cpp_steps_first::<Itype>() {
+            state = self.lcg_step().inverse().apply(state);
+        }
+
+        format!(
+            "{} {} {}",
+            MulMix::multiplier(),
+            self.stream_mix.increment(),
+            state
+        )
+    }
+}
+
+// Reads the text written by the C++ library into a recorded state
+fn parse_cpp_text<Itype, Xtype, OutMix>(text: &str) -> Result<PCGStateInfo<Itype>, PcgError>
+where
+    Itype: PcgOps + Copy + BitSize + Num,
+    Xtype: BitSize,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    let mut fields = text
+        .split_whitespace()
+        .map(|field| Itype::from_str_radix(field, 10).map_err(|_| PcgError::InvalidCppText));
+
+    let mut next = || fields.next().unwrap_or(Err(PcgError::InvalidCppText));
+    let multiplier = next()?;
+    let increment = next()?;
+    let mut state = next()?;
+    if fields.next().is_some() {
+        return Err(PcgError::InvalidCppText);
+    }
+
+    if cpp_steps_first::<Itype>() {
+        state = LcgStep::new(multiplier, increment).apply(state);
+    }
+
+    // The text does not record the widths, the mixin or the stream
+    Ok(PCGStateInfo {
+        state,
+        increment,
+        multiplier,
+        internal_width: Itype::BITS,
+        output_width: Xtype::BITS,
+        output_mixin: OutMix::SERIALIZER_ID.into(),
+        stream: String::new(),
+    })
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype:
+        PcgOps + Copy + Eq + Zero + One + core::ops::BitAnd<Itype, Output = Itype> + BitSize + Num,
+    Xtype: BitSize,
+    StreamMix: RestorableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Reads a PCG written by `operator<<` of the C++ library. The
+    /// multiplier and the increment are checked like `restore_state`
+    /// does.
+    pub fn from_cpp_text(text: &str) -> Result<Self, PcgError> {
+        Self::restore_state(parse_cpp_text::<Itype, Xtype, OutMix>(text)?)
+    }
+}
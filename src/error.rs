@@ -0,0 +1,142 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! The error type used by the fallible parts of this crate.
+
+use core::fmt;
+
+/// The ways seeding a PCG or restoring a saved PCG can fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PcgError {
+    /// The recorded state was made with a different output mixin.
+    #[cfg(feature = "std")]
+    MixinMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// The recorded state was made with a different kind of stream.
+    #[cfg(feature = "std")]
+    StreamMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// The recorded state was made with a different multiplier.
+    MultiplierMismatch,
+    /// The recorded state has a different internal or output width, the
+    /// widths are given as `(internal, output)` in bits.
+    WidthMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The recorded increment is even, no PCG with a stream produces one.
+    EvenIncrement,
+    /// The recorded increment is not the fixed increment of the stream.
+    IncrementMismatch,
+    /// The seed does not hold enough bytes for the requested value.
+    SeedTooShort { needed: usize, available: usize },
+    /// The bytes do not start with the magic number of a saved PCG.
+    BadMagic,
+    /// The bytes were saved in a format version this crate can not read.
+    UnsupportedVersion { found: u16 },
+    /// The bytes do not match their checksum, they were damaged.
+    ChecksumMismatch,
+    /// The saved PCG has a different length than its header describes.
+    LengthMismatch { expected: usize, found: usize },
+    /// The saved PCG has a different number of extension values.
+    ExtSizeMismatch { expected: usize, found: usize },
+    /// The text is not a PCG token like `pcg32-dxsm-setseq:state=0x…:inc=0x…`.
+    InvalidToken,
+    /// The text is not a PCG written by the C++ library.
+    InvalidCppText,
+    /// A field of the PCG does not fit in the bytes the binary format gives
+    /// it, like an id longer than 255 bytes.
+    FieldTooLarge { field: &'static str, value: usize },
+    /// A `SeedSequence` which already spawned `spawned` children can not
+    /// spawn `requested` more without running out of spawn keys.
+    SpawnLimit { spawned: u32, requested: usize },
+}
+
+impl fmt::Display for PcgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            PcgError::MixinMismatch { expected, found } => write!(
+                f,
+                "output mixin {} does not match recorded mixin {}",
+                expected, found
+            ),
+            #[cfg(feature = "std")]
+            PcgError::StreamMismatch { expected, found } => write!(
+                f,
+                "stream {} does not match recorded stream {}",
+                expected, found
+            ),
+            PcgError::MultiplierMismatch => {
+                write!(f, "PCG using different multiplier than recorded state")
+            }
+            PcgError::WidthMismatch { expected, found } => write!(
+                f,
+                "PCG uses {}/{} bit state/output but the recorded state uses {}/{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            PcgError::EvenIncrement => write!(f, "recorded increment is even"),
+            PcgError::IncrementMismatch => {
+                write!(f, "recorded increment does not match the fixed stream")
+            }
+            PcgError::SeedTooShort { needed, available } => write!(
+                f,
+                "seed needs {} more bytes but only {} are left",
+                needed, available
+            ),
+            PcgError::BadMagic => write!(f, "bytes are not a saved PCG"),
+            PcgError::UnsupportedVersion { found } => {
+                write!(f, "saved PCG uses unsupported format version {}", found)
+            }
+            PcgError::ChecksumMismatch => write!(f, "saved PCG does not match its checksum"),
+            PcgError::LengthMismatch { expected, found } => write!(
+                f,
+                "saved PCG should be {} bytes long but is {} bytes",
+                expected, found
+            ),
+            PcgError::ExtSizeMismatch { expected, found } => write!(
+                f,
+                "PCG has {} extension values but the saved PCG has {}",
+                expected, found
+            ),
+            PcgError::InvalidToken => write!(f, "text is not a PCG token"),
+            PcgError::InvalidCppText => write!(f, "text is not a PCG written by pcg-cpp"),
+            PcgError::FieldTooLarge { field, value } => {
+                write!(
+                    f,
+                    "{} of {} is too large for the binary format",
+                    field, value
+                )
+            }
+            PcgError::SpawnLimit { spawned, requested } => write!(
+                f,
+                "sequence with {} children can not spawn {} more",
+                spawned, requested
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PcgError {}
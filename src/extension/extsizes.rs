@@ -39,9 +39,17 @@
 ///  * Ext1024
 /// Bigger extensions can be produced and dropped as long as they are powers of
 /// 2
+///
+/// The extension array is stored inline in the generator, `Storage` is an
+/// array of `EXT_SIZE` elements so no allocation is needed.
 pub trait ExtSize {
     const EXT_SIZE: usize;
     const EXT_BITS: u32;
+
+    type Storage<T>: AsRef<[T]> + AsMut<[T]>;
+
+    /// Builds the extension array, calling `f` once for every element.
+    fn build_storage<T, F: FnMut() -> T>(f: F) -> Self::Storage<T>;
 }
 
 macro_rules! make_ext_size {
@@ -51,6 +59,12 @@ macro_rules! make_ext_size {
         impl ExtSize for $i {
             const EXT_SIZE: usize = $size;
             const EXT_BITS: u32 = $bits;
+
+            type Storage<T> = [T; $size];
+
+            fn build_storage<T, F: FnMut() -> T>(mut f: F) -> Self::Storage<T> {
+                core::array::from_fn(|_| f())
+            }
         })*
     }
 }
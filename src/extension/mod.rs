@@ -24,7 +24,8 @@
 //! K-tuple uniformly.
 //!
 //! These generators require `K*sizeof(Isize)` extra bytes to provide their
-//! equidistribution.
+//! equidistribution. The extension is stored inside the generator, so large
+//! extensions make for a large generator.
 //!
 //! These extended generators are currently in a beta state. They are
 //! implemented according to my understanding of the generator extension
@@ -43,13 +44,13 @@ use super::outputmix::*;
 use super::seeds::PcgSeeder;
 use super::stream::*;
 use super::PcgEngine;
+use core::marker::PhantomData;
 use num_traits::{One, Zero};
 use rand::{
     distributions::{Distribution, Standard},
     Rng,
 };
 use rand_core::{RngCore, SeedableRng};
-use std::marker::PhantomData;
 
 /// An extended PCG generator. These generators provide K-dimensional
 /// equidistribution. Where K is specified by the value of the Size parameter
@@ -62,11 +63,33 @@ pub struct ExtPcg<
     OutMix: OutputMixin<Itype, Xtype>,
     Size: ExtSize,
 > {
-    pcg: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
-    ext: Vec<Xtype>,
+    pub(crate) pcg: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+    pub(crate) ext: Size::Storage<Xtype>,
     _size: PhantomData<Size>,
 }
 
+impl<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+    ExtPcg<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+where
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    Size: ExtSize,
+{
+    // Builds an ExtPcg from a restored PCG and extension array
+    #[cfg(feature = "std")]
+    pub(crate) fn from_parts(
+        pcg: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+        ext: Size::Storage<Xtype>,
+    ) -> Self {
+        ExtPcg {
+            pcg,
+            ext,
+            _size: PhantomData,
+        }
+    }
+}
+
 impl<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
     ExtPcg<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
 where
@@ -87,10 +110,7 @@ where
         let mut pcg = pcg;
 
         //Create the starting extension array
-        let mut ext = Vec::with_capacity(Size::EXT_SIZE);
-        for _ in 0..Size::EXT_SIZE {
-            ext.push(pcg.gen());
-        }
+        let ext = Size::build_storage(|| pcg.gen());
 
         ExtPcg {
             pcg,
@@ -103,7 +123,7 @@ where
 impl<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
     ExtPcg<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
 where
-    Itype: Zero + One,
+    Itype: Zero + One + ::seeds::ReadByteOrder,
     Xtype: PcgOps + BitSize,
     Standard: Distribution<Xtype>,
     StreamMix: Stream<Itype>,
@@ -124,6 +144,21 @@ where
     }
 }
 
+impl<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+    ExtPcg<Itype, Xtype, StreamMix, MulMix, OutMix, Size>
+where
+    StreamMix: SettableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    Size: ExtSize,
+{
+    /// Switches the underlying PCG to the stream `stream_seq`, the state
+    /// and the extension array are kept.
+    pub fn set_stream(&mut self, stream_seq: Itype) {
+        self.pcg.set_stream(stream_seq);
+    }
+}
+
 impl<Itype, StreamMix, MulMix, OutMix, Size> RngCore
     for ExtPcg<Itype, u32, StreamMix, MulMix, OutMix, Size>
 where
@@ -145,8 +180,8 @@ where
         let mask = 2usize.pow(Size::EXT_BITS) - 1;
         let pick = self.pcg.state.as_usize() & mask;
 
-        let ext_val = self.ext[pick];
-        self.ext[pick] += 1;
+        let ext_val = self.ext.as_ref()[pick];
+        self.ext.as_mut()[pick] += 1;
         OutMix::output(
             oldstate,
             self.pcg.stream_mix.increment(),
@@ -192,8 +227,8 @@ where
         let mask = 2usize.pow(Size::EXT_BITS) - 1;
         let pick = self.pcg.state.as_usize() & mask;
 
-        let ext_val = self.ext[pick];
-        self.ext[pick] += 1;
+        let ext_val = self.ext.as_ref()[pick];
+        self.ext.as_mut()[pick] += 1;
         OutMix::output(
             oldstate,
             self.pcg.stream_mix.increment(),
@@ -0,0 +1,173 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! Iterators over the outputs and substreams of a PCG generator.
+
+use multiplier::Multiplier;
+use num_traits::{One, WrappingSub, Zero};
+use numops::PcgOps;
+use outputmix::OutputMixin;
+use stream::Stream;
+use PcgEngine;
+
+/// An iterator over the outputs of a PCG generator.
+///
+/// Iterating steps the generator exactly like calling `next_u32` or
+/// `next_u64`. The iterator also works like a cursor into the stream of the
+/// generator, `step_back` moves it back so `next` returns the previous value
+/// again.
+pub struct Outputs<'a, Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: 'a,
+    Xtype: 'a,
+    StreamMix: Stream<Itype> + 'a,
+    MulMix: Multiplier<Itype> + 'a,
+    OutMix: OutputMixin<Itype, Xtype> + 'a,
+{
+    pcg: &'a mut PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+}
+
+impl<'a, Itype, Xtype, StreamMix, MulMix, OutMix>
+    Outputs<'a, Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + Copy,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    pub(crate) fn new(pcg: &'a mut PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>) -> Self {
+        Outputs { pcg }
+    }
+
+    /// Returns the value the next call to `next` will produce without
+    /// advancing the iterator.
+    pub fn peek(&self) -> Xtype {
+        self.pcg.peek()
+    }
+
+    /// Returns the next `n` values the iterator will produce without
+    /// advancing the iterator.
+    #[cfg(feature = "std")]
+    pub fn peek_n(&self, n: usize) -> Vec<Xtype> {
+        self.pcg.peek_n(n)
+    }
+}
+
+impl<'a, Itype, Xtype, StreamMix, MulMix, OutMix> Iterator
+    for Outputs<'a, Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + Copy,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    type Item = Xtype;
+
+    #[inline]
+    fn next(&mut self) -> Option<Xtype> {
+        Some(self.pcg.step())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<'a, Itype, Xtype, StreamMix, MulMix, OutMix>
+    Outputs<'a, Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps
+        + Copy
+        + One
+        + Zero
+        + Ord
+        + Eq
+        + core::ops::BitAnd<Itype, Output = Itype>
+        + core::ops::ShrAssign
+        + WrappingSub
+        + 'static,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Moves the iterator back one value and returns it, the next call to
+    /// `next` returns the same value again.
+    pub fn step_back(&mut self) -> Xtype {
+        self.pcg.step_back();
+        self.pcg.peek()
+    }
+}
+
+/// An endless iterator of generators which are evenly spaced along the same
+/// stream. Created by `PcgEngine::substreams`.
+pub struct Substreams<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    next: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+    spacing: Itype,
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> Substreams<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    pub(crate) fn new(
+        first: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+        spacing: Itype,
+    ) -> Self {
+        Substreams {
+            next: first,
+            spacing,
+        }
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> Iterator
+    for Substreams<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps
+        + Copy
+        + One
+        + Zero
+        + Ord
+        + Eq
+        + core::ops::BitAnd<Itype, Output = Itype>
+        + core::ops::ShrAssign
+        + 'static,
+    StreamMix: Stream<Itype> + Clone,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    type Item = PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let out = self.next.clone();
+        self.next.advance(self.spacing);
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! The algebra of the LCG that drives every PCG generator.
+//!
+//! A single step of the LCG maps the state `x` to `mult * x + plus`. Taking
+//! several steps in a row is again a map of the same form, which is what
+//! makes jumping ahead, stepping back and leapfrogging cheap. `LcgStep`
+//! represents one of these maps.
+//!
+//! ```
+//! extern crate pcg_rand;
+//! extern crate rand;
+//!
+//! use pcg_rand::Pcg32;
+//! use rand::{RngCore, SeedableRng};
+//!
+//! let mut ra = Pcg32::from_entropy();
+//! let mut rb = ra.clone();
+//!
+//! //Taking one step 10 times is the same as taking 10 steps once
+//! let step = ra.lcg_step().pow(10);
+//! ra.apply_step(&step);
+//! rb.advance(10);
+//!
+//! assert_eq!(ra.next_u32(), rb.next_u32());
+//! ```
+
+use num_traits::{One, WrappingSub, Zero};
+use numops::{mul_inverse, PcgOps};
+
+/// An affine transition of the LCG state, mapping `x` to `mult * x + plus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LcgStep<Itype> {
+    pub mult: Itype,
+    pub plus: Itype,
+}
+
+impl<Itype> LcgStep<Itype> {
+    /// Creates a step which maps `x` to `mult * x + plus`.
+    pub const fn new(mult: Itype, plus: Itype) -> LcgStep<Itype> {
+        LcgStep { mult, plus }
+    }
+}
+
+impl<Itype> LcgStep<Itype>
+where
+    Itype: PcgOps + Copy,
+{
+    /// Applies this step to `state`.
+    #[inline]
+    pub fn apply(&self, state: Itype) -> Itype {
+        self.mult.wrap_mul(state).wrap_add(self.plus)
+    }
+
+    /// Returns the step which first applies `self` and then `next`.
+    #[inline]
+    pub fn compose(&self, next: &LcgStep<Itype>) -> LcgStep<Itype> {
+        LcgStep {
+            mult: next.mult.wrap_mul(self.mult),
+            plus: next.mult.wrap_mul(self.plus).wrap_add(next.plus),
+        }
+    }
+
+    /// Returns this step with its increment multiplied by `factor`.
+    ///
+    /// A step that was computed for an increment of one becomes the step
+    /// for an increment of `factor`, this is how the jump tables of
+    /// `Multiplier` are shared between every stream.
+    #[inline]
+    pub fn scale_increment(&self, factor: Itype) -> LcgStep<Itype> {
+        LcgStep {
+            mult: self.mult,
+            plus: self.plus.wrap_mul(factor),
+        }
+    }
+}
+
+impl<Itype> LcgStep<Itype>
+where
+    Itype: PcgOps
+        + Copy
+        + One
+        + Zero
+        + Ord
+        + Eq
+        + core::ops::BitAnd<Itype, Output = Itype>
+        + core::ops::ShrAssign,
+{
+    /// The step which does not change the state.
+    pub fn identity() -> LcgStep<Itype> {
+        LcgStep {
+            mult: Itype::one(),
+            plus: Itype::zero(),
+        }
+    }
+
+    /// Returns the step which applies this step `n` times.
+    pub fn pow(&self, n: Itype) -> LcgStep<Itype> {
+        let mut cur = *self;
+        let mut acc = LcgStep::identity();
+        let mut n = n;
+
+        while n > Itype::zero() {
+            if (n & Itype::one()) != Itype::zero() {
+                acc = acc.compose(&cur);
+            }
+
+            cur = cur.compose(&cur);
+            n >>= Itype::one();
+        }
+
+        acc
+    }
+
+    /// Returns the step which undoes this step.
+    ///
+    /// Only steps with an odd multiplier can be undone, which includes
+    /// every step of a PCG generator.
+    pub fn inverse(&self) -> LcgStep<Itype>
+    where
+        Itype: WrappingSub,
+    {
+        let inv_mult = mul_inverse(self.mult);
+        LcgStep {
+            mult: inv_mult,
+            plus: Itype::zero().wrapping_sub(&inv_mult.wrap_mul(self.plus)),
+        }
+    }
+}
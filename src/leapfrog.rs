@@ -0,0 +1,140 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+//! Leapfrog generators split the sequence of a PCG generator between several
+//! consumers by giving each of them every `n`th output. This is useful for
+//! parallelizing a loop over random values without changing its results.
+//!
+//! ```
+//! extern crate pcg_rand;
+//! extern crate rand;
+//!
+//! use pcg_rand::Pcg32;
+//! use rand::{RngCore, SeedableRng};
+//!
+//! let mut pcg = Pcg32::from_entropy();
+//! let mut even = pcg.leapfrog(0, 2);
+//! let mut odd = pcg.leapfrog(1, 2);
+//!
+//! assert_eq!(even.next_u32(), pcg.next_u32());
+//! assert_eq!(odd.next_u32(), pcg.next_u32());
+//! ```
+
+use lcg::LcgStep;
+use multiplier::Multiplier;
+use numops::PcgOps;
+use outputmix::OutputMixin;
+use rand_core::RngCore;
+use stream::Stream;
+use PcgEngine;
+
+/// A PCG generator which skips ahead several steps of its parent generator
+/// for every output. Created by `PcgEngine::leapfrog`.
+///
+/// Each output is produced with the output function of the parent generator,
+/// only the state transition is replaced by the combined transition of
+/// several steps.
+pub struct Leapfrog<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    pcg: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+    step: LcgStep<Itype>,
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> Leapfrog<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + Copy,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    pub(crate) fn new(
+        pcg: PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>,
+        step: LcgStep<Itype>,
+    ) -> Self {
+        Leapfrog { pcg, step }
+    }
+
+    #[inline]
+    fn step(&mut self) -> Xtype {
+        let oldstate = self.pcg.state;
+        self.pcg.state = self.step.apply(oldstate);
+
+        OutMix::output(
+            oldstate,
+            self.pcg.stream_mix.increment(),
+            MulMix::multiplier(),
+        )
+    }
+}
+
+//Provide random for 32 bit generators
+impl<Itype, StreamMix, MulMix, OutMix> RngCore for Leapfrog<Itype, u32, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + Copy,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, u32>,
+{
+    fn next_u32(&mut self) -> u32 {
+        self.step()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        ::rand_core::impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        ::rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ::rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+//Provide random for 64 bit generators
+impl<Itype, StreamMix, MulMix, OutMix> RngCore for Leapfrog<Itype, u64, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + Copy,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, u64>,
+{
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        ::rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ::rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -38,6 +38,9 @@
 //!   of numbers. *NOTE*: This means that you may not get consistant results across runs of your
 //!   program. If the memory location of your PCG moves for any reason such as the state of the
 //!   allocator being different you will get a different stream of numbers.
+//! * `Pcg32Counted` : Same as `Pcg32Unique` but the sequence is taken from a counter when the RNG
+//!   is created and stored. The sequence does not change when the RNG is moved and the RNG can be
+//!   serialized.
 //!
 //!
 //! # Usage
@@ -47,7 +50,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! pcg_rand = "0.13.0"
+//! pcg_rand = "0.14.0"
 //! ```
 //! # Typename Nomenclature
 //! This library attempts to simplify using the PCG generators by defining easy
@@ -67,6 +70,8 @@
 //! * `Setseq`: This is a settable stream. The random number stream can be set manually.
 //! * `Unique`: This is a unique stream. Each instance of this type will be given a unique stream
 //!   that cannot be modified.
+//! * `Counted`: Like `Unique` each instance is given its own stream, but the stream comes from a
+//!   counter and is stored so it survives moving the generator.
 //! * `Oneseq`: This is one fixed random sequence. It is hardcoded into the library and should be
 //!   good enough to give good "randomness".
 //! * `Mcg`: This has no random sequence it degenerates the internal LCG into a MCG. This is for
@@ -119,6 +124,11 @@
 //! //Create from another PCG
 //! let ext2 : ExtPcg<_,_,_,_,_,Ext256> = ExtPcg::from_pcg(Pcg32Unique::from_entropy());
 //! ```
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate core;
+
 extern crate byteorder;
 extern crate num_traits;
 extern crate rand;
@@ -127,16 +137,28 @@ extern crate rand_core;
 #[cfg(feature = "serde1")]
 extern crate serde;
 
+#[cfg(feature = "std")]
+use rand::Rng;
 use rand_core::{RngCore, SeedableRng};
 
-use std::num::Wrapping;
+use core::num::Wrapping;
 
+#[cfg(feature = "std")]
+pub mod binary;
+mod consts;
+#[cfg(feature = "std")]
+mod cpp;
+pub mod error;
 pub mod extension;
+pub mod iter;
+pub mod lcg;
+pub mod leapfrog;
 pub mod multiplier;
 pub mod numops;
 pub mod outputmix;
 pub mod seeds;
 pub mod stream;
+mod token;
 
 #[cfg(feature = "serde1")]
 pub mod serialization;
@@ -144,19 +166,33 @@ pub mod serialization;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
+pub use error::PcgError;
+use lcg::LcgStep;
 use multiplier::{DefaultMultiplier, McgMultiplier, Multiplier};
-use num_traits::{One, Zero};
+use num_traits::{FromPrimitive, One, PrimInt, WrappingSub, Zero};
 use numops::*;
 use outputmix::{DXsMMixin, OutputMixin, XshRrMixin, XshRsMixin};
 use seeds::PcgSeeder;
-use stream::{NoSeqStream, OneSeqStream, SpecificSeqStream, Stream, UniqueSeqStream};
+#[cfg(target_has_atomic = "ptr")]
+use stream::CountedSeqStream;
+#[cfg(feature = "std")]
+use stream::RestorableStream;
+use stream::{
+    NoSeqStream, OneSeqStream, SettableStream, SpecificSeqStream, StableStream, Stream,
+    UniqueSeqStream,
+};
 
-use std::marker::PhantomData;
+use core::fmt;
+use core::marker::PhantomData;
 
 /// A generic PCG structure.
 ///
 /// This structure allows the building of many types of PCG generators by using various
 /// Mixins for both the stream, multiplier, and permutation function.
+///
+/// Printing a PCG with `Display` gives a one line token such as
+/// `pcg32-dxsm-setseq:state=0x…:inc=0x…`, which `parse` turns back into the
+/// exact same generator.
 pub struct PcgEngine<
     Itype,
     Xtype,
@@ -165,12 +201,33 @@ pub struct PcgEngine<
     OutMix: OutputMixin<Itype, Xtype>,
 > {
     state: Itype,
+    origin: Itype,
     stream_mix: StreamMix,
     mul_mix: PhantomData<MulMix>,
     out_mix: PhantomData<OutMix>,
     phantom: PhantomData<Xtype>,
 }
 
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> Clone
+    for PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: Clone,
+    StreamMix: Stream<Itype> + Clone,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    fn clone(&self) -> Self {
+        PcgEngine {
+            state: self.state.clone(),
+            origin: self.origin.clone(),
+            stream_mix: self.stream_mix.clone(),
+            mul_mix: PhantomData,
+            out_mix: PhantomData,
+            phantom: PhantomData,
+        }
+    }
+}
+
 impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
 where
     Itype: Zero,
@@ -189,6 +246,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
 where
     Itype: Copy + BitSize,
@@ -206,13 +264,18 @@ where
             internal_width: Itype::BITS,
             output_width: Xtype::BITS,
             output_mixin: OutMix::SERIALIZER_ID.into(),
+            stream: StreamMix::SERIALIZER_ID.into(),
         }
     }
 
+    /// Restores a PCG from a given state without checking that the state
+    /// was made by the same kind of PCG. Prefer `restore_state` where it is
+    /// available.
     pub fn restore_state_with_no_verification(state: PCGStateInfo<Itype>) -> Self {
         PcgEngine {
             state: state.state,
-            stream_mix: StreamMix::build(Some(state.increment)),
+            origin: state.state,
+            stream_mix: StreamMix::from_increment(state.increment),
             mul_mix: PhantomData,
             out_mix: PhantomData,
             phantom: PhantomData,
@@ -220,38 +283,116 @@ where
     }
 }
 
-impl<Itype, Xtype, MulMix, OutMix> PcgEngine<Itype, Xtype, SpecificSeqStream<Itype>, MulMix, OutMix>
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
 where
-    Itype: Copy + Eq + Zero + BitSize,
+    StreamMix: SettableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Switches the PCG to the stream `stream_seq`, the state is kept.
+    ///
+    /// This only exists for PCGs whose stream can be changed, like
+    /// `SpecificSeqStream`.
+    pub fn set_stream(&mut self, stream_seq: Itype) {
+        self.stream_mix.set_stream(stream_seq);
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
     SpecificSeqStream<Itype>: Stream<Itype>,
+{
+    /// Converts this PCG into one with a `SpecificSeqStream` which continues
+    /// with exactly the same outputs.
+    ///
+    /// The new PCG uses the increment this PCG has where it is right now, so
+    /// this can be used to freeze a PCG with a `UniqueSeqStream` before it
+    /// moves, or to save and restore PCGs whose stream type can not be
+    /// restored. This borrows the PCG because moving it into the call would
+    /// already change the stream of a `UniqueSeqStream`.
+    pub fn to_setseq(&self) -> PcgEngine<Itype, Xtype, SpecificSeqStream<Itype>, MulMix, OutMix>
+    where
+        Itype: Copy,
+    {
+        PcgEngine {
+            state: self.state,
+            origin: self.origin,
+            stream_mix: SpecificSeqStream::from_increment(self.stream_mix.increment()),
+            mul_mix: PhantomData,
+            out_mix: PhantomData,
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: Copy + Eq + Zero + One + core::ops::BitAnd<Itype, Output = Itype> + BitSize,
     Xtype: BitSize,
+    StreamMix: Stream<Itype>,
     MulMix: Multiplier<Itype>,
     OutMix: OutputMixin<Itype, Xtype>,
 {
-    // Restores a PCG from a given state and verifies that all the parameters match the recorded state
-    pub fn restore_state(state: PCGStateInfo<Itype>) -> Result<Self, String> {
+    // Checks that the recorded state was made by a PCG with the same parameters
+    fn verify_state(state: &PCGStateInfo<Itype>) -> Result<(), PcgError> {
         if OutMix::SERIALIZER_ID != state.output_mixin {
-            return Err("Output Mixin type does not match recorded state".into());
+            return Err(PcgError::MixinMismatch {
+                expected: OutMix::SERIALIZER_ID,
+                found: state.output_mixin.clone(),
+            });
+        }
+
+        // States saved by older versions do not record the stream
+        if !state.stream.is_empty() && StreamMix::SERIALIZER_ID != state.stream {
+            return Err(PcgError::StreamMismatch {
+                expected: StreamMix::SERIALIZER_ID,
+                found: state.stream.clone(),
+            });
         }
 
         if MulMix::multiplier() != state.multiplier {
-            return Err("PCG using different multiplier than recorded state".into());
+            return Err(PcgError::MultiplierMismatch);
         }
 
-        if Xtype::BITS != state.output_width {
-            return Err("PCG uses different output size than recorded state".into());
+        if Xtype::BITS != state.output_width || Itype::BITS != state.internal_width {
+            return Err(PcgError::WidthMismatch {
+                expected: (Itype::BITS, Xtype::BITS),
+                found: (state.internal_width, state.output_width),
+            });
         }
 
-        if Itype::BITS != state.internal_width {
-            return Err("PCG uses different internal size than recorded state".into());
+        // An increment of zero is allowed for PCGs which were turned into an MCG
+        if state.increment & Itype::one() == Itype::zero() && state.increment != Itype::zero() {
+            return Err(PcgError::EvenIncrement);
         }
 
-        let mut stream = SpecificSeqStream::new();
-        stream.set_stream(state.increment);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: Copy + Eq + Zero + One + core::ops::BitAnd<Itype, Output = Itype> + BitSize,
+    Xtype: BitSize,
+    StreamMix: RestorableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    // Restores a PCG from a given state and verifies that all the parameters match the recorded state.
+    // The stream checks that it could have recorded the increment. The position is not recorded,
+    // so the restored PCG counts its position from the restored state.
+    pub fn restore_state(state: PCGStateInfo<Itype>) -> Result<Self, PcgError> {
+        Self::verify_state(&state)?;
 
         Ok(PcgEngine {
             state: state.state,
-            stream_mix: stream,
+            origin: state.state,
+            stream_mix: StreamMix::restore(state.increment)?,
             mul_mix: PhantomData,
             out_mix: PhantomData,
             phantom: PhantomData,
@@ -323,6 +464,74 @@ where
     }
 }
 
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + Copy,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Steps the generator forward and produces the output for the state it
+    /// was in before the step.
+    #[inline]
+    fn step(&mut self) -> Xtype {
+        let oldstate = self.state;
+        self.state = self
+            .stream_mix
+            .increment()
+            .wrap_add(oldstate.wrap_mul(MulMix::multiplier()));
+
+        OutMix::output(oldstate, self.stream_mix.increment(), MulMix::multiplier())
+    }
+
+    /// Returns the LCG transition of a single step of this generator.
+    pub fn lcg_step(&self) -> LcgStep<Itype> {
+        LcgStep::new(MulMix::multiplier(), self.stream_mix.increment())
+    }
+
+    /// Moves the state of this generator with an arbitrary LCG transition.
+    ///
+    /// To stay on the stream of this generator `step` should be derived
+    /// from `lcg_step` or `jump_step`.
+    pub fn apply_step(&mut self, step: &LcgStep<Itype>) {
+        self.state = step.apply(self.state);
+    }
+
+    /// Returns the next output of the generator without advancing it.
+    pub fn peek(&self) -> Xtype {
+        OutMix::output(
+            self.state,
+            self.stream_mix.increment(),
+            MulMix::multiplier(),
+        )
+    }
+
+    /// Returns the next `n` outputs of the generator without advancing it.
+    #[cfg(feature = "std")]
+    pub fn peek_n(&self, n: usize) -> Vec<Xtype> {
+        let mut state = self.state;
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            out.push(OutMix::output(
+                state,
+                self.stream_mix.increment(),
+                MulMix::multiplier(),
+            ));
+            state = self
+                .stream_mix
+                .increment()
+                .wrap_add(state.wrap_mul(MulMix::multiplier()));
+        }
+        out
+    }
+
+    /// Returns an iterator over the outputs of this generator. The iterator
+    /// can also step back along the stream, see `iter::Outputs` for details.
+    pub fn outputs(&mut self) -> iter::Outputs<'_, Itype, Xtype, StreamMix, MulMix, OutMix> {
+        iter::Outputs::new(self)
+    }
+}
+
 impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
 where
     Itype: PcgOps
@@ -331,31 +540,318 @@ where
         + Zero
         + Ord
         + Eq
-        + std::ops::BitAnd<Itype, Output = Itype>
-        + std::ops::ShrAssign,
+        + core::ops::BitAnd<Itype, Output = Itype>
+        + core::ops::ShrAssign
+        + 'static,
     StreamMix: Stream<Itype>,
     MulMix: Multiplier<Itype>,
     OutMix: OutputMixin<Itype, Xtype>,
 {
     pub fn advance(&mut self, delta: Itype) {
-        let mut cur_mult = MulMix::multiplier();
-        let mut cur_plus = self.stream_mix.increment();
+        let step = self.jump_step(delta);
+        self.apply_step(&step);
+    }
+
+    /// Returns the LCG transition which has the same effect as taking
+    /// `delta` steps with this generator.
+    pub fn jump_step(&self, delta: Itype) -> LcgStep<Itype> {
+        match MulMix::jump_table() {
+            Some(table) => self.compose_jumps(table, delta),
+            None => self.lcg_step().pow(delta),
+        }
+    }
+
+    /// Combines the jumps from a table in the format of
+    /// `Multiplier::jump_table` for every bit set in `delta`.
+    fn compose_jumps(&self, table: &[LcgStep<Itype>], delta: Itype) -> LcgStep<Itype> {
         let mut delta = delta;
-        let mut acc_mult = Itype::one();
-        let mut acc_plus = Itype::zero();
+        let mut acc = LcgStep::identity();
 
-        while delta > Itype::zero() {
+        for jump in table {
+            if delta == Itype::zero() {
+                break;
+            }
             if (delta & Itype::one()) != Itype::zero() {
-                acc_mult = acc_mult.wrap_mul(cur_mult);
-                acc_plus = acc_plus.wrap_mul(cur_mult).wrap_add(cur_plus);
+                acc = acc.compose(jump);
             }
-
-            cur_plus = cur_mult.wrap_add(Itype::one()).wrap_mul(cur_plus);
-            cur_mult = cur_mult.wrap_mul(cur_mult);
             delta >>= Itype::one();
         }
 
-        self.state = acc_mult.wrap_mul(self.state).wrap_add(acc_plus);
+        acc.scale_increment(self.stream_mix.increment())
+    }
+
+    /// Returns the output this generator would produce after taking `index`
+    /// steps, without changing the state of the generator.
+    pub fn output_at(&self, index: Itype) -> Xtype {
+        OutMix::output(
+            self.jump_step(index).apply(self.state),
+            self.stream_mix.increment(),
+            MulMix::multiplier(),
+        )
+    }
+
+    /// Returns the outputs this generator would produce after taking each of
+    /// the number of steps in `indices`, without changing the state of the
+    /// generator.
+    ///
+    /// This is faster than calling `output_at` for every index because the
+    /// jumps for every power of two are only computed once.
+    #[cfg(feature = "std")]
+    pub fn outputs_at(&self, indices: &[Itype]) -> Vec<Xtype> {
+        //Multipliers without a precomputed table get one which is just big
+        //enough for the largest index
+        let mut local_table = Vec::new();
+        let table = match MulMix::jump_table() {
+            Some(table) => table,
+            None => {
+                let mut jump = LcgStep::new(MulMix::multiplier(), Itype::one());
+                let mut largest = indices.iter().cloned().max().unwrap_or_else(Itype::zero);
+                while largest > Itype::zero() {
+                    local_table.push(jump);
+                    jump = jump.compose(&jump);
+                    largest >>= Itype::one();
+                }
+                &local_table[..]
+            }
+        };
+
+        indices
+            .iter()
+            .map(|&index| {
+                OutMix::output(
+                    self.compose_jumps(table, index).apply(self.state),
+                    self.stream_mix.increment(),
+                    MulMix::multiplier(),
+                )
+            })
+            .collect()
+    }
+
+    /// Creates a generator which produces every `n`th output of this
+    /// generator, starting with output `k`. Giving each of `n` workers the
+    /// generator for a different `k` interleaves their outputs into exactly
+    /// the sequence this generator would have produced.
+    pub fn leapfrog(
+        &self,
+        k: Itype,
+        n: Itype,
+    ) -> leapfrog::Leapfrog<Itype, Xtype, StreamMix, MulMix, OutMix>
+    where
+        StreamMix: StableStream<Itype>,
+    {
+        let mut pcg = self.clone();
+        pcg.advance(k);
+        leapfrog::Leapfrog::new(pcg, self.jump_step(n))
+    }
+
+    /// Moves the generator back by `delta` steps. This is the inverse of
+    /// `advance`.
+    pub fn retreat(&mut self, delta: Itype)
+    where
+        Itype: WrappingSub,
+    {
+        self.advance(Itype::zero().wrapping_sub(&delta));
+    }
+
+    /// Moves the generator back a single step, so the next output will be
+    /// the same as the previous output.
+    ///
+    /// This runs the LCG backwards using the inverse of the multiplier so it
+    /// takes constant time.
+    pub fn step_back(&mut self)
+    where
+        Itype: WrappingSub,
+    {
+        let step = self.lcg_step().inverse();
+        self.apply_step(&step);
+    }
+
+    /// Computes how many steps `self` would have to `advance` to reach the
+    /// current state of `other`. This is the inverse of `advance`, so after
+    /// `a.advance(a.distance(&b))` both generators produce the same output.
+    ///
+    /// Both generators have to be on the same stream for the result to be
+    /// meaningful.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state of `other` can never be reached from the state of
+    /// `self`. This can only happen for MCG generators, which split their
+    /// state space into several disjoint cycles.
+    pub fn distance(&self, other: &Self) -> Itype {
+        self.state_distance(self.state, other.state)
+    }
+
+    /// Reports how many steps this generator has taken since it was seeded
+    /// or restored. Steps taken with `advance` or `retreat` are included.
+    pub fn position(&self) -> Itype {
+        self.state_distance(self.origin, self.state)
+    }
+
+    /// Computes how many steps it takes to get from state `from` to state
+    /// `to` on the stream of this generator.
+    fn state_distance(&self, from: Itype, to: Itype) -> Itype {
+        let mut cur_step = self.lcg_step();
+        let mut cur_state = from;
+        let mut the_bit = self.lowest_step_bit(from);
+
+        //Each doubling of the step size moves the bit it changes up by one
+        let mut dist_bit = Itype::one();
+        let mut distance = Itype::zero();
+
+        while cur_state != to {
+            if the_bit == Itype::zero() || dist_bit == Itype::zero() {
+                panic!("Generator state can not be reached from this generator");
+            }
+
+            if (cur_state & the_bit) != (to & the_bit) {
+                cur_state = cur_step.apply(cur_state);
+                distance = distance.wrap_add(dist_bit);
+            }
+
+            the_bit = the_bit.wrap_add(the_bit);
+            dist_bit = dist_bit.wrap_add(dist_bit);
+            cur_step = cur_step.compose(&cur_step);
+        }
+
+        distance
+    }
+
+    /// Finds the lowest bit of `state` that a single step changes. For an
+    /// LCG this is always the lowest bit, but an MCG never changes its lowest
+    /// few bits which also shortens its period. Returns zero if `state` is a
+    /// fixed point.
+    fn lowest_step_bit(&self, state: Itype) -> Itype {
+        let stepped = self.lcg_step().apply(state);
+        let mut the_bit = Itype::one();
+        while the_bit != Itype::zero() && (stepped & the_bit) == (state & the_bit) {
+            the_bit = the_bit.wrap_add(the_bit);
+        }
+        the_bit
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + PrimInt + FromPrimitive + core::ops::ShrAssign + 'static,
+    StreamMix: StableStream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    /// Splits the period of this generator into `n` equally sized blocks and
+    /// returns a generator starting at the beginning of each block. All of
+    /// the generators are on the same stream and the first one is a copy of
+    /// this generator, their sequences will not overlap unless one of them
+    /// produces more than a full block of outputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero or larger than the period of the generator.
+    #[cfg(feature = "std")]
+    pub fn split_into(&self, n: usize) -> Vec<Self> {
+        let n = Itype::from_usize(n).expect("Number of blocks does not fit in the generator state");
+        assert!(
+            n != Itype::zero(),
+            "Can not split a generator into zero blocks"
+        );
+
+        //A single block is the whole period, which does not fit in the state
+        if n == Itype::one() {
+            return vec![self.clone()];
+        }
+
+        //Compute 2^BITS / n without overflowing the state type
+        let mut spacing = (Itype::max_value() - (n - Itype::one())) / n + Itype::one();
+
+        //An MCG has a shorter period so the blocks have to shrink with it
+        let the_bit = self.lowest_step_bit(self.state);
+        if the_bit != Itype::zero() {
+            spacing = spacing / the_bit;
+        }
+        assert!(
+            spacing != Itype::zero(),
+            "Can not split a generator into more blocks than its period"
+        );
+
+        self.substreams(spacing)
+            .take(n.to_usize().unwrap())
+            .collect()
+    }
+
+    /// Returns an endless iterator of generators on the same stream as this
+    /// one. The first generator is a copy of this one and every following
+    /// generator is `spacing` steps further along the stream.
+    pub fn substreams(
+        &self,
+        spacing: Itype,
+    ) -> iter::Substreams<Itype, Xtype, StreamMix, MulMix, OutMix> {
+        iter::Substreams::new(self.clone(), spacing)
+    }
+}
+
+// The engine the workers of the parallel fills use
+#[cfg(feature = "std")]
+type SetseqEngine<Itype, Xtype, MulMix, OutMix> =
+    PcgEngine<Itype, Xtype, SpecificSeqStream<Itype>, MulMix, OutMix>;
+
+#[cfg(feature = "std")]
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps + PrimInt + FromPrimitive + core::ops::ShrAssign + 'static,
+    Xtype: BitSize,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    SpecificSeqStream<Itype>: Stream<Itype>,
+    SetseqEngine<Itype, Xtype, MulMix, OutMix>: RngCore + Send,
+{
+    /// Fills `dest` with random bytes using `threads` worker threads.
+    ///
+    /// Every worker jumps ahead to its part of the buffer with `advance`, so
+    /// the result is exactly the same as calling `fill_bytes` and this
+    /// generator is left in the same state, regardless of the number of
+    /// threads used. The workers use a copy made with `to_setseq`, so this
+    /// also works for a `UniqueSeqStream`.
+    pub fn fill_bytes_parallel(&mut self, dest: &mut [u8], threads: usize) {
+        self.fill_parallel(dest, threads, RngCore::fill_bytes);
+    }
+
+    /// Fills `dest` with random `u32` values using `threads` worker threads.
+    ///
+    /// Like `fill_bytes_parallel` the result and the final state of the
+    /// generator are the same as filling `dest` sequentially with `Rng::fill`.
+    pub fn fill_u32_parallel(&mut self, dest: &mut [u32], threads: usize) {
+        self.fill_parallel(dest, threads, |rng, chunk| rng.fill(chunk));
+    }
+
+    fn fill_parallel<T: Send>(
+        &mut self,
+        dest: &mut [T],
+        threads: usize,
+        fill: fn(&mut SetseqEngine<Itype, Xtype, MulMix, OutMix>, &mut [T]),
+    ) {
+        let elem_bytes = core::mem::size_of::<T>();
+        let output_bytes = Xtype::BITS / 8;
+        let total_bytes = core::mem::size_of_val(dest);
+
+        //Sequential filling produces 8 bytes at a time, so every worker has to
+        //start on a multiple of 8 bytes to see the same outputs
+        let align = 8 / elem_bytes;
+        let threads = threads.max(1);
+        let per_thread = (dest.len() + threads - 1) / threads;
+        let per_thread = ((per_thread + align - 1) / align * align).max(align);
+
+        std::thread::scope(|scope| {
+            for (i, chunk) in dest.chunks_mut(per_thread).enumerate() {
+                let mut rng = self.to_setseq();
+                let offset = i * per_thread * elem_bytes / output_bytes;
+                rng.advance(Itype::from_usize(offset).unwrap());
+                scope.spawn(move || fill(&mut rng, chunk));
+            }
+        });
+
+        let consumed = (total_bytes + output_bytes - 1) / output_bytes;
+        self.advance(Itype::from_usize(consumed).unwrap());
     }
 }
 
@@ -365,6 +861,14 @@ pub type OneseqDXsM6432 = PcgEngine<u64, u32, OneSeqStream, DefaultMultiplier, D
 pub type UniqueXshRs6432 = PcgEngine<u64, u32, UniqueSeqStream, DefaultMultiplier, XshRsMixin>;
 pub type UniqueXshRr6432 = PcgEngine<u64, u32, UniqueSeqStream, DefaultMultiplier, XshRrMixin>;
 pub type UniqueDXsM6432 = PcgEngine<u64, u32, UniqueSeqStream, DefaultMultiplier, DXsMMixin>;
+#[cfg(target_has_atomic = "ptr")]
+pub type CountedXshRs6432 =
+    PcgEngine<u64, u32, CountedSeqStream<u64>, DefaultMultiplier, XshRsMixin>;
+#[cfg(target_has_atomic = "ptr")]
+pub type CountedXshRr6432 =
+    PcgEngine<u64, u32, CountedSeqStream<u64>, DefaultMultiplier, XshRrMixin>;
+#[cfg(target_has_atomic = "ptr")]
+pub type CountedDXsM6432 = PcgEngine<u64, u32, CountedSeqStream<u64>, DefaultMultiplier, DXsMMixin>;
 pub type SetseqXshRs6432 =
     PcgEngine<u64, u32, SpecificSeqStream<u64>, DefaultMultiplier, XshRsMixin>;
 pub type SetseqXshRr6432 =
@@ -380,6 +884,10 @@ pub type Pcg32 = SetseqDXsM6432;
 pub type Pcg32Oneseq = OneseqDXsM6432;
 /// A helper definition for a 32bit PCG which has a unique random stream for each instance
 pub type Pcg32Unique = UniqueDXsM6432;
+/// A helper definition for a 32bit PCG which has a unique random stream for each instance that
+/// stays the same when the PCG is moved
+#[cfg(target_has_atomic = "ptr")]
+pub type Pcg32Counted = CountedDXsM6432;
 /// A helper definition for a 32bit PCG which is fast but may lack statistical quality.
 ///
 /// This generator sacrifices quality for speed by utilizing a Multiplicative Congruential
@@ -399,6 +907,15 @@ pub type UniqueXshRs12832 = PcgEngine<u128, u32, UniqueSeqStream, DefaultMultipl
 pub type UniqueXshRr12832 = PcgEngine<u128, u32, UniqueSeqStream, DefaultMultiplier, XshRrMixin>;
 #[cfg(feature = "u128")]
 pub type UniqueDXsM12832 = PcgEngine<u128, u32, UniqueSeqStream, DefaultMultiplier, DXsMMixin>;
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type CountedXshRs12832 =
+    PcgEngine<u128, u32, CountedSeqStream<u128>, DefaultMultiplier, XshRsMixin>;
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type CountedXshRr12832 =
+    PcgEngine<u128, u32, CountedSeqStream<u128>, DefaultMultiplier, XshRrMixin>;
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type CountedDXsM12832 =
+    PcgEngine<u128, u32, CountedSeqStream<u128>, DefaultMultiplier, DXsMMixin>;
 #[cfg(feature = "u128")]
 pub type SetseqXshRs12832 =
     PcgEngine<u128, u32, SpecificSeqStream<u128>, DefaultMultiplier, XshRsMixin>;
@@ -429,6 +946,11 @@ pub type Pcg32LOneseq = OneseqDXsM12832;
 /// This makes it potentially slower but it has a longer period.
 #[cfg(feature = "u128")]
 pub type Pcg32LUnique = UniqueDXsM12832;
+/// A helper definition for a 32bit PCG which has a unique random stream for each instance that
+/// stays the same when the PCG is moved. This version uses 128bits of internal state
+/// This makes it potentially slower but it has a longer period.
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type Pcg32LCounted = CountedDXsM12832;
 /// A helper definition for a 32bit PCG which is fast but may lack statistical quality.
 ///
 /// This generator sacrifices quality for speed by utilizing a Multiplicative Congruential
@@ -450,6 +972,15 @@ pub type UniqueXshRs12864 = PcgEngine<u128, u64, UniqueSeqStream, DefaultMultipl
 pub type UniqueXshRr12864 = PcgEngine<u128, u64, UniqueSeqStream, DefaultMultiplier, XshRrMixin>;
 #[cfg(feature = "u128")]
 pub type UniqueDXsM12864 = PcgEngine<u128, u64, UniqueSeqStream, DefaultMultiplier, DXsMMixin>;
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type CountedXshRs12864 =
+    PcgEngine<u128, u64, CountedSeqStream<u128>, DefaultMultiplier, XshRsMixin>;
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type CountedXshRr12864 =
+    PcgEngine<u128, u64, CountedSeqStream<u128>, DefaultMultiplier, XshRrMixin>;
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type CountedDXsM12864 =
+    PcgEngine<u128, u64, CountedSeqStream<u128>, DefaultMultiplier, DXsMMixin>;
 #[cfg(feature = "u128")]
 pub type SetseqXshRs12864 =
     PcgEngine<u128, u64, SpecificSeqStream<u128>, DefaultMultiplier, XshRsMixin>;
@@ -475,6 +1006,10 @@ pub type Pcg64Oneseq = OneseqDXsM12864;
 /// A helper definition for a 64bit PCG which has a unique random stream for each instance
 #[cfg(feature = "u128")]
 pub type Pcg64Unique = UniqueDXsM12864;
+/// A helper definition for a 64bit PCG which has a unique random stream for each instance that
+/// stays the same when the PCG is moved.
+#[cfg(all(feature = "u128", target_has_atomic = "ptr"))]
+pub type Pcg64Counted = CountedDXsM12864;
 /// A helper definition for a 64bit PCG which is fast but may lack statistical quality.
 ///
 /// This generator sacrifices quality for speed by utilizing a Multiplicative Congruential
@@ -483,6 +1018,112 @@ pub type Pcg64Unique = UniqueDXsM12864;
 #[cfg(feature = "u128")]
 pub type Pcg64Fast = McgXshRs12864;
 
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: PcgOps
+        + Copy
+        + One
+        + Zero
+        + Ord
+        + Eq
+        + core::ops::BitAnd<Itype, Output = Itype>
+        + core::ops::ShrAssign
+        + seeds::ReadByteOrder
+        + 'static,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>: SeedableRng<Seed = PcgSeeder<Itype>>,
+{
+    /// Describes the seed and stream this generator was created with and
+    /// how far it has moved since then.
+    ///
+    /// Generators which were restored from a `PCGStateInfo` report the
+    /// restored state as their seed.
+    pub fn provenance(&self) -> Provenance<Itype> {
+        Provenance {
+            seed: self.origin,
+            stream: self.stream_mix.get_stream(),
+            offset: self.position(),
+        }
+    }
+
+    /// Rebuilds the generator described by `provenance`.
+    ///
+    /// Generators with a `CountedSeqStream` get back the stream they were
+    /// built with. Generators with a `UniqueSeqStream` can not be rebuilt
+    /// because their stream depends on where they are in memory.
+    ///
+    /// ```compile_fail
+    /// extern crate pcg_rand;
+    /// extern crate rand;
+    ///
+    /// use pcg_rand::Pcg32Unique;
+    /// use rand::SeedableRng;
+    ///
+    /// let pcg = Pcg32Unique::from_entropy();
+    /// let copy = Pcg32Unique::from_provenance(&pcg.provenance());
+    /// ```
+    pub fn from_provenance(provenance: &Provenance<Itype>) -> Self
+    where
+        StreamMix: StableStream<Itype>,
+    {
+        let mut pcg = PcgEngine {
+            state: provenance.seed,
+            origin: provenance.seed,
+            stream_mix: StreamMix::from_stream(provenance.stream),
+            mul_mix: PhantomData,
+            out_mix: PhantomData,
+            phantom: PhantomData,
+        };
+        pcg.advance(provenance.offset);
+        pcg
+    }
+}
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: seeds::ReadByteOrder,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+    PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>: SeedableRng<Seed = PcgSeeder<Itype>>,
+{
+    /// Creates a generator from an arbitrary byte string such as a name.
+    /// The same key gives the same generator on every platform and in every
+    /// version of this crate, see `PcgSeeder::from_key`.
+    ///
+    /// ```
+    /// extern crate pcg_rand;
+    /// extern crate rand;
+    ///
+    /// use pcg_rand::Pcg32;
+    /// use rand::RngCore;
+    ///
+    /// let mut ra = Pcg32::from_key(b"level-3/boss");
+    /// let mut rb = Pcg32::from_key(b"level-3/boss");
+    /// assert_eq!(ra.next_u32(), rb.next_u32());
+    /// ```
+    pub fn from_key(key: &[u8]) -> Self {
+        Self::from_seed(PcgSeeder::from_key(key))
+    }
+
+    /// Creates a generator from any value which implements `Hash`, see
+    /// `PcgSeeder::from_hashable`.
+    pub fn from_hashable<K: core::hash::Hash + ?Sized>(key: &K) -> Self {
+        Self::from_seed(PcgSeeder::from_hashable(key))
+    }
+
+    /// Creates a generator from a seed which is scrambled first, see
+    /// `PcgSeeder::scrambled`. Use this when seeds are picked by hand.
+    pub fn from_scrambled_seed(seed: PcgSeeder<Itype>) -> Self
+    where
+        Itype: seeds::Scramble,
+    {
+        Self::from_seed(seed.scrambled())
+    }
+}
+
 //
 // Seeding for all of the different RNG types
 //
@@ -490,7 +1131,7 @@ pub type Pcg64Fast = McgXshRs12864;
 impl<Itype, Xtype, StreamMix, MulMix, OutMix> SeedableRng
     for PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
 where
-    Itype: Sized + seeds::ReadByteOrder + Zero + One,
+    Itype: Sized + Copy + seeds::ReadByteOrder + Zero + One,
     StreamMix: Stream<Itype>,
     MulMix: Multiplier<Itype>,
     OutMix: OutputMixin<Itype, Xtype>,
@@ -498,10 +1139,12 @@ where
 {
     type Seed = PcgSeeder<Itype>;
 
-    fn from_seed(mut seed: Self::Seed) -> Self {
+    fn from_seed(seed: Self::Seed) -> Self {
+        let (state, stream) = seed.parts();
         PcgEngine {
-            state: seed.get(),
-            stream_mix: StreamMix::build(Some(seed.get())),
+            state,
+            origin: state,
+            stream_mix: StreamMix::build(Some(stream)),
             mul_mix: PhantomData::<MulMix>,
             out_mix: PhantomData::<OutMix>,
             phantom: PhantomData::<Xtype>,
@@ -534,6 +1177,39 @@ impl Pcg32Basic {
     }
 }
 
+// Pcg32Basic is the same generator as SetseqXshRr6432, so it shares its state format
+#[cfg(feature = "std")]
+impl Pcg32Basic {
+    /// Gets the current state of the PCG
+    pub fn get_state(&self) -> PCGStateInfo<u64> {
+        PCGStateInfo {
+            state: self.state,
+            increment: self.inc | 1,
+            multiplier: <DefaultMultiplier as Multiplier<u64>>::multiplier(),
+            internal_width: <u64 as BitSize>::BITS,
+            output_width: <u32 as BitSize>::BITS,
+            output_mixin: <XshRrMixin as OutputMixin<u64, u32>>::SERIALIZER_ID.into(),
+            stream: <SpecificSeqStream<u64> as Stream<u64>>::SERIALIZER_ID.into(),
+        }
+    }
+
+    // Restores a PCG from a given state and verifies that all the parameters match the recorded state
+    pub fn restore_state(state: PCGStateInfo<u64>) -> Result<Pcg32Basic, PcgError> {
+        SetseqXshRr6432::verify_state(&state)?;
+
+        // Pcg32Basic always sets the low bit of the increment, so unlike the
+        // engines it can not restore an MCG with an increment of zero
+        if state.increment & 1 == 0 {
+            return Err(PcgError::EvenIncrement);
+        }
+
+        Ok(Pcg32Basic {
+            state: state.state,
+            inc: state.increment,
+        })
+    }
+}
+
 //Pcg32Basic is an rng
 impl RngCore for Pcg32Basic {
     fn next_u32(&mut self) -> u32 {
@@ -567,14 +1243,19 @@ impl RngCore for Pcg32Basic {
 impl SeedableRng for Pcg32Basic {
     type Seed = PcgSeeder<u64>;
 
-    fn from_seed(mut seed: Self::Seed) -> Pcg32Basic {
-        Pcg32Basic {
-            state: seed.get(),
-            inc: seed.get(),
-        }
+    fn from_seed(seed: Self::Seed) -> Pcg32Basic {
+        let (state, inc) = seed.parts();
+        Pcg32Basic { state, inc }
     }
 }
 
+/// The internal state of a generator as returned by `get_state`, with enough
+/// information to check that it is restored into the same kind of generator.
+///
+/// It does not record where the generator was seeded. A restored generator
+/// reports a `position` of zero and its restored state as the seed of its
+/// `provenance`, keep the `Provenance` as well to log how it was made.
+#[cfg(feature = "std")]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct PCGStateInfo<Itype> {
     pub state: Itype,
@@ -583,4 +1264,29 @@ pub struct PCGStateInfo<Itype> {
     pub internal_width: usize,
     pub output_width: usize,
     pub output_mixin: String,
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub stream: String,
+}
+
+/// Describes how to rebuild a generator from scratch: seed it with `seed` on
+/// stream `stream`, then advance it by `offset` steps.
+///
+/// Unlike the raw state this is meaningful to a reader, for example in the
+/// logs of an experiment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Provenance<Itype> {
+    pub seed: Itype,
+    pub stream: Itype,
+    pub offset: Itype,
+}
+
+impl<Itype: fmt::Display> fmt::Display for Provenance<Itype> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "seed {}, stream {}, draw {}",
+            self.seed, self.stream, self.offset
+        )
+    }
 }
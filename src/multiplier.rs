@@ -24,35 +24,92 @@
  *     http://www.pcg-random.org
  */
 
+use lcg::LcgStep;
+
 /// This trait provides the multiplier for the internal LCG of the PCG generator
 /// Implementing this trait for a struct will allow providing your own
 /// multiplier for the PCG.
 pub trait Multiplier<Itype> {
     fn multiplier() -> Itype;
+
+    /// A table of precomputed jumps used to advance the generator quickly.
+    ///
+    /// Entry `k` holds the step which jumps `2^k` steps ahead on the stream
+    /// with an increment of one. Because the increment only scales the
+    /// `plus` part of the step, see `LcgStep::scale_increment`, one table per
+    /// multiplier serves every stream.
+    ///
+    /// Returning `None` makes the generator compute the jumps each time it
+    /// is advanced.
+    fn jump_table() -> Option<&'static [LcgStep<Itype>]> {
+        None
+    }
+}
+
+/// A multiplier which is available as a constant, which lets generators
+/// using it step in `const` code.
+pub trait ConstMultiplier<Itype>: Multiplier<Itype> {
+    const MULTIPLIER: Itype;
 }
 
+macro_rules! make_jump_table {
+    ( $( $name:ident => $t:ty);* ) => {
+        $(
+        const fn $name(mult: $t) -> [LcgStep<$t>; <$t>::BITS as usize] {
+            let mut table = [LcgStep::new(0, 0); <$t>::BITS as usize];
+            let mut cur_mult = mult;
+            let mut cur_sum = 1;
+            let mut i = 0;
+            while i < table.len() {
+                table[i] = LcgStep::new(cur_mult, cur_sum);
+                cur_sum = cur_mult.wrapping_add(1).wrapping_mul(cur_sum);
+                cur_mult = cur_mult.wrapping_mul(cur_mult);
+                i += 1;
+            }
+            table
+        })*
+    }
+}
+
+make_jump_table!(
+    jump_table_u8 => u8;
+    jump_table_u16 => u16;
+    jump_table_u32 => u32;
+    jump_table_u64 => u64;
+    jump_table_u128 => u128
+);
+
 /// Provides a default "good" multiplier based on the multiplier provided
 /// in the C++ implementation of PCG
 pub struct DefaultMultiplier;
 
 macro_rules! make_default_mul {
-	( $( $t:ty => $e:expr);* ) => {
+	( $( $t:ty => $table:ident, $e:expr);* ) => {
 		$(
 		impl Multiplier<$t> for DefaultMultiplier {
 			#[inline]
 			fn multiplier() -> $t {
 				$e
 			}
+
+			fn jump_table() -> Option<&'static [LcgStep<$t>]> {
+				static TABLE: [LcgStep<$t>; <$t>::BITS as usize] = $table($e);
+				Some(&TABLE)
+			}
+		}
+
+		impl ConstMultiplier<$t> for DefaultMultiplier {
+			const MULTIPLIER: $t = $e;
 		})*
 	}
 }
 
 make_default_mul!(
-    u8 => 141u8;
-    u16 => 12829u16;
-    u32 => 747_796_405u32;
-    u64 => 6_364_136_223_846_793_005u64;
-    u128 => 47_026_247_687_942_121_848_144_207_491_837_523_525u128 //u128::from_parts(2549297995355413924, 4865540595714422341)
+    u8 => jump_table_u8, 141u8;
+    u16 => jump_table_u16, 12829u16;
+    u32 => jump_table_u32, 747_796_405u32;
+    u64 => jump_table_u64, 6_364_136_223_846_793_005u64;
+    u128 => jump_table_u128, 47_026_247_687_942_121_848_144_207_491_837_523_525u128 //u128::from_parts(2549297995355413924, 4865540595714422341)
 );
 
 /// Provides a default "good" multiplier based on the multiplier provided
@@ -60,21 +117,30 @@ make_default_mul!(
 pub struct McgMultiplier;
 
 macro_rules! make_mcg_mul {
-	     ( $( $t:ty => $e:expr);* ) => {
+	     ( $( $t:ty => $table:ident, $e:expr);* ) => {
 	       $(
 		impl Multiplier<$t> for McgMultiplier {
 			#[inline]
 			fn multiplier() -> $t {
 				$e
 			}
+
+			fn jump_table() -> Option<&'static [LcgStep<$t>]> {
+				static TABLE: [LcgStep<$t>; <$t>::BITS as usize] = $table($e);
+				Some(&TABLE)
+			}
+		}
+
+		impl ConstMultiplier<$t> for McgMultiplier {
+			const MULTIPLIER: $t = $e;
 		})*
 	}
 }
 
 make_mcg_mul!(
-    u8 => 217u8;
-    u16 => 62169u16;
-    u32 => 277_803_737u32;
-    u64 => 12_605_985_483_714_917_081u64;
-    u128 => 327_738_287_884_841_127_335_028_083_622_016_905_945u128//u128::from_parts(17766728186571221404,12605985483714917081)
+    u8 => jump_table_u8, 217u8;
+    u16 => jump_table_u16, 62169u16;
+    u32 => jump_table_u32, 277_803_737u32;
+    u64 => jump_table_u64, 12_605_985_483_714_917_081u64;
+    u128 => jump_table_u128, 327_738_287_884_841_127_335_028_083_622_016_905_945u128//u128::from_parts(17766728186571221404,12605985483714917081)
 );
@@ -17,6 +17,8 @@
  *
  */
 
+use num_traits::{One, WrappingSub};
+
 /// The types of numaric options that PCG needs to operate.
 /// Some day this will be replaced with Num-traits when they support
 /// wrapping opts for everything, and when extprim supports those traits as
@@ -79,6 +81,26 @@ basic_ops!(
     u128, 128
 );
 
+/// Computes the multiplicative inverse of an odd number modulo 2^BITS.
+///
+/// This uses Newton's method, every iteration doubles the number of correct
+/// bits. Any odd number is its own inverse modulo 8 so we start with three
+/// correct bits.
+pub fn mul_inverse<T>(val: T) -> T
+where
+    T: PcgOps + Copy + One + Eq + WrappingSub,
+{
+    let two = T::one().wrap_add(T::one());
+    let mut inv = val;
+    loop {
+        let next = inv.wrap_mul(two.wrapping_sub(&val.wrap_mul(inv)));
+        if next == inv {
+            return inv;
+        }
+        inv = next;
+    }
+}
+
 macro_rules! smaller {
     ( $( $t:ty, $other:ty);*) => {
         $(
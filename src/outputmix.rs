@@ -24,9 +24,9 @@
  *     http://www.pcg-random.org
  */
 
+use core::ops::{BitOr, BitXor, Shr};
 use num_traits::{One, PrimInt};
 use numops::*;
-use std::ops::{BitOr, BitXor, Shr};
 
 /// The output mixin trait provides the permutation function for the output
 /// of the PCG. After the LCG state is advanced the state is run through
@@ -174,3 +174,114 @@ where
         hi.wrap_mul(low)
     }
 }
+
+// Versions of the output functions for concrete types which can be used in
+// `const` code. They have to match the generic versions above exactly, the
+// const tests compare every pair.
+macro_rules! make_const_output {
+    ( $( $name:ident : $i:ty => $x:ty );* ) => {
+        $(
+        impl XshRsMixin {
+            pub const fn $name(state: $i, _multiplier: $i) -> $x {
+                let ibits = <$i>::BITS as usize;
+                let xbits = <$x>::BITS as usize;
+                let sparebits = ibits - xbits;
+
+                let opbits: usize = if sparebits - 5 >= 64 {
+                    5
+                } else if sparebits - 4 >= 32 {
+                    4
+                } else if sparebits - 3 >= 16 {
+                    3
+                } else if sparebits - 2 >= 4 {
+                    2
+                } else if sparebits > 1 {
+                    1
+                } else {
+                    0
+                };
+                let mask = (1 << opbits) - 1;
+                let maxrandshift = mask;
+                let topspare = opbits;
+                let bottomspare = sparebits - topspare;
+                let xshift = topspare + (xbits + maxrandshift) / 2;
+
+                let rshift = if opbits != 0 {
+                    (state >> (ibits - opbits)) as usize & mask
+                } else {
+                    0
+                };
+
+                let state = state ^ (state >> xshift);
+                (state >> (bottomspare - maxrandshift + rshift)) as $x
+            }
+        }
+
+        impl XshRrMixin {
+            pub const fn $name(state: $i, _multiplier: $i) -> $x {
+                let ibits = <$i>::BITS as usize;
+                let xtypebits = <$x>::BITS as usize;
+                let sparebits = ibits - xtypebits;
+
+                let wantedopbits: usize = if xtypebits >= 128 {
+                    7
+                } else if xtypebits >= 64 {
+                    6
+                } else if xtypebits >= 32 {
+                    5
+                } else if xtypebits >= 16 {
+                    4
+                } else {
+                    3
+                };
+
+                let opbits: usize = if sparebits >= wantedopbits {
+                    wantedopbits
+                } else {
+                    sparebits
+                };
+
+                let amplifier = wantedopbits - opbits;
+                let mask = (1 << opbits) - 1;
+                let topspare = opbits;
+                let bottomspare = sparebits - topspare;
+                let xshift = (topspare + xtypebits) / 2;
+
+                let rot = if opbits != 0 {
+                    (state >> (ibits - opbits)) as usize & mask
+                } else {
+                    0
+                };
+
+                let amprot = (rot << amplifier) & mask;
+                let state = state ^ (state >> xshift);
+
+                let result = (state >> bottomspare) as $x;
+                result.rotate_right(amprot as u32)
+            }
+        }
+
+        impl DXsMMixin {
+            pub const fn $name(state: $i, multiplier: $i) -> $x {
+                let xbits = <$x>::BITS;
+                let hi = (state >> (<$i>::BITS - xbits)) as $x;
+                let low = state as $x;
+
+                let low = low | 1;
+                let hi = hi ^ (hi >> (xbits / 2));
+
+                let hi = hi.wrapping_mul(multiplier as $x);
+
+                let hi = hi ^ (hi >> (3 * xbits / 4));
+
+                hi.wrapping_mul(low)
+            }
+        })*
+    }
+}
+
+make_const_output!(
+    output_u64_u32: u64 => u32;
+    output_u128_u32: u128 => u32;
+    output_u128_u64: u128 => u64
+);
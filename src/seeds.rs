@@ -1,17 +1,69 @@
+//! Seeds for the PCG generators.
+//!
+//! The seed data of a `PcgSeeder<T>` is `2 * size_of::<T>()` bytes with a
+//! fixed layout, it is stored inline and does not allocate. The first
+//! `size_of::<T>()` bytes are the initial state and the remaining bytes are
+//! the stream, both stored as little endian integers. For `u128` this means
+//! the low 64 bits come first.
+//!
+//! ```
+//! extern crate pcg_rand;
+//!
+//! use pcg_rand::seeds::PcgSeeder;
+//!
+//! let seeder = PcgSeeder::<u32>::from_bytes([1, 0, 0, 0, 2, 0, 0, 0]);
+//! assert_eq!(seeder.parts(), (1, 2));
+//! ```
+//!
+//! Before version 0.14 the halves of a `u128` were stored high half first.
+//! Raw seeds saved by those versions can be read with
+//! `PcgSeeder::from_legacy_bytes`.
+//!
+//! Seeds can also be derived from keys such as names or tuples with
+//! `PcgSeeder::from_key` and `PcgSeeder::from_hashable`. These use
+//! `StableHasher`, whose output is fixed and will not change between
+//! versions of this crate.
+//!
+//! Seeds which were picked by hand, like `0, 1, 2, ...`, start their
+//! generators at neighbouring states. `PcgSeeder::scrambled` runs the seed
+//! and the stream through a bijective `Scramble` permutation first, so
+//! adjacent seeds give unrelated starting states.
+//!
+//! To seed many generators from one experiment seed use a `SeedSequence`,
+//! which mixes the seed so the generators start far apart.
+
+use core::convert::AsMut;
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+use core::default::Default;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::size_of;
 use num_traits::Zero;
-use std::convert::AsMut;
-use std::default::Default;
-use std::marker::PhantomData;
-use std::mem::size_of;
 
 use byteorder::{ByteOrder, LE};
+use error::PcgError;
+use multiplier::{ConstMultiplier, McgMultiplier};
 
 pub trait ReadByteOrder {
+    /// The bytes of a seed and a stream of this type.
+    type SeedBytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy;
+
     fn read(src: &[u8]) -> Self;
     fn write(&self, dest: &mut [u8]);
-}
 
+    /// Reads a value stored by versions before 0.14, this only differs from
+    /// `read` for `u128`.
+    fn read_legacy(src: &[u8]) -> Self
+    where
+        Self: Sized,
+    {
+        Self::read(src)
+    }
+}
 impl ReadByteOrder for u8 {
+    type SeedBytes = [u8; 2];
+
     fn read(src: &[u8]) -> Self {
         src[0]
     }
@@ -22,6 +74,8 @@ impl ReadByteOrder for u8 {
 }
 
 impl ReadByteOrder for u16 {
+    type SeedBytes = [u8; 4];
+
     fn read(src: &[u8]) -> Self {
         LE::read_u16(src)
     }
@@ -32,6 +86,8 @@ impl ReadByteOrder for u16 {
 }
 
 impl ReadByteOrder for u32 {
+    type SeedBytes = [u8; 8];
+
     fn read(src: &[u8]) -> Self {
         LE::read_u32(src)
     }
@@ -42,6 +98,8 @@ impl ReadByteOrder for u32 {
 }
 
 impl ReadByteOrder for u64 {
+    type SeedBytes = [u8; 16];
+
     fn read(src: &[u8]) -> Self {
         LE::read_u64(src)
     }
@@ -52,34 +110,125 @@ impl ReadByteOrder for u64 {
 }
 
 impl ReadByteOrder for u128 {
+    type SeedBytes = [u8; 32];
+
     fn read(src: &[u8]) -> Self {
+        LE::read_u128(src)
+    }
+
+    fn write(&self, dest: &mut [u8]) {
+        LE::write_u128(dest, *self);
+    }
+
+    fn read_legacy(src: &[u8]) -> Self {
         let top = u128::from(LE::read_u64(src));
         let bottom = u128::from(LE::read_u64(&src[size_of::<u64>()..]));
 
         (top << 64) | bottom
     }
+}
 
-    fn write(&self, dest: &mut [u8]) {
-        let top = (*self >> 64) as u64;
-        let bottom = *self as u64;
-        LE::write_u64(dest, top);
-        LE::write_u64(&mut dest[size_of::<u64>()..], bottom);
+/// A bijective mixing function for seed values.
+///
+/// This is the RXS-M-XS permutation of the C++ library: a random xorshift, a
+/// multiplication by the MCG multiplier and a final xorshift. Distinct
+/// values always stay distinct, but values which are close together end up
+/// far apart.
+pub trait Scramble {
+    fn scramble(self) -> Self;
+}
+
+macro_rules! make_scramble {
+    ( $( $t:ty => $opbits:expr ),* ) => {
+        $(
+        impl Scramble for $t {
+            fn scramble(self) -> $t {
+                const BITS: u32 = <$t>::BITS;
+                const OPBITS: u32 = $opbits;
+                let rshift = (self >> (BITS - OPBITS)) as u32 & ((1 << OPBITS) - 1);
+                let mut val = self ^ (self >> (OPBITS + rshift));
+                val = val.wrapping_mul(<McgMultiplier as ConstMultiplier<$t>>::MULTIPLIER);
+                val ^ (val >> ((2 * BITS + 2) / 3))
+            }
+        })*
     }
 }
 
+make_scramble!(u8 => 2, u16 => 3, u32 => 4, u64 => 5, u128 => 6);
+
+/// The seed of a PCG generator, a seed value for the initial state and a
+/// stream. See the module documentation for the byte layout.
 #[derive(Clone)]
-pub struct PcgSeeder<T> {
-    data: Vec<u8>,
+pub struct PcgSeeder<T: ReadByteOrder> {
+    data: T::SeedBytes,
     at_pos: usize,
     _type: PhantomData<T>,
 }
 
-impl<T> AsMut<[u8]> for PcgSeeder<T> {
+impl<T: ReadByteOrder> AsMut<[u8]> for PcgSeeder<T> {
     fn as_mut(&mut self) -> &mut [u8] {
         self.data.as_mut()
     }
 }
 
+impl<T: ReadByteOrder> PcgSeeder<T> {
+    /// Builds a seeder from its raw bytes.
+    pub fn from_bytes(data: T::SeedBytes) -> PcgSeeder<T> {
+        PcgSeeder {
+            data,
+            at_pos: 0,
+            _type: PhantomData,
+        }
+    }
+
+    /// Builds a seeder from raw bytes in the layout used before version 0.14.
+    /// The seeder gives the same seed and stream the old version did.
+    pub fn from_legacy_bytes(data: T::SeedBytes) -> PcgSeeder<T> {
+        let (seed_data, stream_data) = data.as_ref().split_at(size_of::<T>());
+        PcgSeeder::seed_with_stream(T::read_legacy(seed_data), T::read_legacy(stream_data))
+    }
+
+    /// Returns the raw bytes of the seed.
+    pub fn to_bytes(&self) -> T::SeedBytes {
+        self.data
+    }
+
+    /// Builds a seeder from a seed value and a stream.
+    pub fn seed_with_stream(seed: T, stream: T) -> PcgSeeder<T> {
+        let mut data = T::SeedBytes::default();
+        {
+            let (seed_data, stream_data) = data.as_mut().split_at_mut(size_of::<T>());
+            seed.write(seed_data);
+            stream.write(stream_data);
+        }
+
+        PcgSeeder::from_bytes(data)
+    }
+
+    /// Returns the seed value and the stream.
+    pub fn parts(&self) -> (T, T) {
+        let (seed_data, stream_data) = self.data.as_ref().split_at(size_of::<T>());
+        (T::read(seed_data), T::read(stream_data))
+    }
+
+    /// Reads the next value from the seed, fails if there are not enough
+    /// bytes left.
+    pub fn get(&mut self) -> Result<T, PcgError> {
+        let data = self.data.as_ref();
+        let available = data.len() - self.at_pos;
+        if size_of::<T>() > available {
+            return Err(PcgError::SeedTooShort {
+                needed: size_of::<T>(),
+                available,
+            });
+        }
+
+        let out = T::read(&data[self.at_pos..]);
+        self.at_pos += size_of::<T>();
+        Ok(out)
+    }
+}
+
 /*
  * The default seeds will control what seeds are used by `new_unseeded` calls.
  * These values were chosen at random.
@@ -117,34 +266,340 @@ impl Default for PcgSeeder<u8> {
     }
 }
 
+impl<T: ReadByteOrder> PcgSeeder<T> {
+    /// Builds a seeder from an arbitrary byte string, for example a name
+    /// given by a user. The same key always gives the same seed.
+    pub fn from_key(key: &[u8]) -> PcgSeeder<T> {
+        let mut hasher = StableHasher::new();
+        hasher.write(key);
+        PcgSeeder::from_digest(hasher.digest())
+    }
+
+    /// Builds a seeder from any value which implements `Hash`. The value is
+    /// hashed with `StableHasher`, so the seed only changes if the `Hash`
+    /// implementation of the value changes.
+    ///
+    /// This is not the same as `from_key` even for byte slices, because
+    /// hashing a slice also hashes its length.
+    pub fn from_hashable<K: Hash + ?Sized>(key: &K) -> PcgSeeder<T> {
+        let mut hasher = StableHasher::new();
+        key.hash(&mut hasher);
+        PcgSeeder::from_digest(hasher.digest())
+    }
+
+    // Spreads a 128 bit digest over all the bytes of the seed
+    fn from_digest(digest: u128) -> PcgSeeder<T> {
+        let mut data = T::SeedBytes::default();
+        for (i, chunk) in data.as_mut().chunks_mut(size_of::<u128>()).enumerate() {
+            let block = mix128(digest.wrapping_add(i as u128)).to_le_bytes();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        PcgSeeder::from_bytes(data)
+    }
+}
+
+impl<T: ReadByteOrder + Scramble> PcgSeeder<T> {
+    /// Returns this seed with the seed value and the stream both run through
+    /// `Scramble`. Seeding with `PcgSeeder::seed(1).scrambled()` and
+    /// `PcgSeeder::seed(2).scrambled()` gives unrelated starting states.
+    ///
+    /// ```
+    /// extern crate pcg_rand;
+    /// extern crate rand;
+    ///
+    /// use pcg_rand::seeds::PcgSeeder;
+    /// use pcg_rand::Pcg32;
+    /// use rand::SeedableRng;
+    ///
+    /// let replicas: Vec<Pcg32> = (0..8)
+    ///     .map(|i| Pcg32::from_seed(PcgSeeder::seed(i).scrambled()))
+    ///     .collect();
+    /// ```
+    pub fn scrambled(&self) -> PcgSeeder<T> {
+        let (seed, stream) = self.parts();
+        PcgSeeder::seed_with_stream(seed.scramble(), stream.scramble())
+    }
+}
+
 impl<T: Sized + ReadByteOrder + Zero> PcgSeeder<T> {
+    /// Builds a seeder from a seed value on stream zero.
     pub fn seed(seed: T) -> PcgSeeder<T> {
         PcgSeeder::seed_with_stream(seed, T::zero())
     }
+}
 
-    pub fn seed_with_stream(seed: T, stream: T) -> PcgSeeder<T> {
-        let mut data = vec![0; size_of::<T>() * 2];
-        {
-            let (seed_data, stream_data) = data.split_at_mut(size_of::<T>());
-            seed.write(seed_data);
-            stream.write(stream_data);
+// A bijective mixing function used to turn a digest into seed bytes
+fn mix128(mut val: u128) -> u128 {
+    const MULT: u128 = 47_026_247_687_942_121_848_144_207_491_837_523_525;
+    val ^= val >> 64;
+    val = val.wrapping_mul(MULT);
+    val ^= val >> 64;
+    val = val.wrapping_mul(MULT);
+    val ^ (val >> 64)
+}
+
+/// A 128 bit FNV-1a hasher whose output is pinned, it gives the same result
+/// on every platform and in every version of this crate.
+///
+/// All integers are hashed as little endian bytes, and `usize` and `isize`
+/// are hashed as 64 bit values.
+#[derive(Clone, Debug)]
+pub struct StableHasher {
+    state: u128,
+}
+
+impl StableHasher {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    pub fn new() -> StableHasher {
+        StableHasher {
+            state: StableHasher::OFFSET_BASIS,
+        }
+    }
+
+    /// Returns the full 128 bit hash of the bytes written so far.
+    pub fn digest(&self) -> u128 {
+        self.state
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        (self.state as u64) ^ ((self.state >> 64) as u64)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= u128::from(byte);
+            self.state = self.state.wrapping_mul(StableHasher::PRIME);
         }
+    }
 
-        PcgSeeder {
-            data,
-            at_pos: 0,
-            _type: PhantomData,
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i]);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+/// An entropy pool which turns one seed of any length into many well
+/// separated seeds, modeled on `SeedSequence` from NumPy.
+///
+/// The entropy is hashed into a small pool, and every child created with
+/// `spawn` mixes in its position in the tree of spawned sequences. Seeds
+/// from different sequences are unrelated even if the entropy values were
+/// close together, like `0, 1, 2, ...`.
+///
+/// ```
+/// extern crate pcg_rand;
+/// extern crate rand;
+///
+/// use pcg_rand::seeds::SeedSequence;
+/// use pcg_rand::Pcg32;
+/// use rand::SeedableRng;
+///
+/// let mut root = SeedSequence::new(20_261_016);
+/// let replicas: Vec<Pcg32> = root
+///     .spawn(1000)
+///     .unwrap()
+///     .iter()
+///     .map(|seq| Pcg32::from_seed(seq.seeder()))
+///     .collect();
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeedSequence {
+    entropy: Vec<u32>,
+    spawn_key: Vec<u32>,
+    pool: [u32; SeedSequence::POOL_SIZE],
+    children_spawned: u32,
+}
+
+#[cfg(feature = "std")]
+impl SeedSequence {
+    const POOL_SIZE: usize = 4;
+    const INIT_A: u32 = 0x43b0_d7e5;
+    const MULT_A: u32 = 0x931e_8875;
+    const INIT_B: u32 = 0x8b51_f9dd;
+    const MULT_B: u32 = 0x58f3_8ded;
+    const MIX_MULT_L: u32 = 0xca01_f9dd;
+    const MIX_MULT_R: u32 = 0x4973_f715;
+    const XSHIFT: u32 = 16;
+
+    /// Creates a sequence from an integer seed.
+    pub fn new(entropy: u128) -> SeedSequence {
+        let mut words = Vec::new();
+        let mut rest = entropy;
+        loop {
+            words.push(rest as u32);
+            rest >>= 32;
+            if rest == 0 {
+                break;
+            }
         }
+        SeedSequence::from_words(&words)
     }
 
-    pub fn get(&mut self) -> T {
-        //For now we panic if there aren't enough bytes
-        if size_of::<T>() > (self.data.len() - self.at_pos) {
-            panic!("Not enough bytes left in the seed");
+    /// Creates a sequence from entropy of any length, given as 32 bit words
+    /// with the least significant word first.
+    pub fn from_words(entropy: &[u32]) -> SeedSequence {
+        SeedSequence::with_spawn_key(entropy.to_vec(), Vec::new())
+    }
+
+    fn with_spawn_key(entropy: Vec<u32>, spawn_key: Vec<u32>) -> SeedSequence {
+        let mut assembled = entropy.clone();
+        if !spawn_key.is_empty() && assembled.len() < SeedSequence::POOL_SIZE {
+            assembled.resize(SeedSequence::POOL_SIZE, 0);
         }
+        assembled.extend_from_slice(&spawn_key);
 
-        let out = T::read(&self.data[self.at_pos..]);
-        self.at_pos += size_of::<T>();
-        out
+        SeedSequence {
+            pool: SeedSequence::mix_entropy(&assembled),
+            entropy,
+            spawn_key,
+            children_spawned: 0,
+        }
+    }
+
+    /// The position of this sequence in the tree of spawned sequences, empty
+    /// for a sequence created directly from entropy.
+    pub fn spawn_key(&self) -> &[u32] {
+        &self.spawn_key
+    }
+
+    /// Creates `n` child sequences. Every call gives new children, so
+    /// spawning 2 and then 3 children is the same as spawning 5.
+    ///
+    /// Every child is identified by a 32 bit word in its spawn key, so this
+    /// fails with `PcgError::SpawnLimit` and spawns nothing if the sequence
+    /// would have more than `u32::MAX` children in total.
+    pub fn spawn(&mut self, n: usize) -> Result<Vec<SeedSequence>, PcgError> {
+        let first = self.children_spawned;
+        let last = u32::try_from(n)
+            .ok()
+            .and_then(|n| first.checked_add(n))
+            .ok_or(PcgError::SpawnLimit {
+                spawned: first,
+                requested: n,
+            })?;
+        self.children_spawned = last;
+
+        Ok((first..last)
+            .map(|i| {
+                let mut key = self.spawn_key.clone();
+                key.push(i);
+                SeedSequence::with_spawn_key(self.entropy.clone(), key)
+            })
+            .collect())
+    }
+
+    /// Fills `dest` with words of seed material.
+    pub fn generate_state(&self, dest: &mut [u32]) {
+        let mut hash_const = SeedSequence::INIT_B;
+        for (word, &pool_val) in dest.iter_mut().zip(self.pool.iter().cycle()) {
+            let mut val = pool_val ^ hash_const;
+            hash_const = hash_const.wrapping_mul(SeedSequence::MULT_B);
+            val = val.wrapping_mul(hash_const);
+            *word = val ^ (val >> SeedSequence::XSHIFT);
+        }
+    }
+
+    /// Builds a seed for a generator with state type `T` from this sequence.
+    pub fn seeder<T: ReadByteOrder>(&self) -> PcgSeeder<T> {
+        let mut data = T::SeedBytes::default();
+        let mut words = [0u32; 2 * size_of::<u128>() / size_of::<u32>()];
+        self.generate_state(&mut words);
+
+        for (chunk, word) in data.as_mut().chunks_mut(size_of::<u32>()).zip(words.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+        }
+        PcgSeeder::from_bytes(data)
+    }
+
+    // Hashes the entropy into the pool
+    fn mix_entropy(entropy: &[u32]) -> [u32; SeedSequence::POOL_SIZE] {
+        let mut hash_const = SeedSequence::INIT_A;
+        let mut hashmix = |value: u32| {
+            let mut value = value ^ hash_const;
+            hash_const = hash_const.wrapping_mul(SeedSequence::MULT_A);
+            value = value.wrapping_mul(hash_const);
+            value ^ (value >> SeedSequence::XSHIFT)
+        };
+        let mix = |x: u32, y: u32| {
+            let result = SeedSequence::MIX_MULT_L
+                .wrapping_mul(x)
+                .wrapping_sub(SeedSequence::MIX_MULT_R.wrapping_mul(y));
+            result ^ (result >> SeedSequence::XSHIFT)
+        };
+
+        let mut pool = [0u32; SeedSequence::POOL_SIZE];
+        for (i, slot) in pool.iter_mut().enumerate() {
+            *slot = hashmix(entropy.get(i).cloned().unwrap_or(0));
+        }
+
+        for i_src in 0..SeedSequence::POOL_SIZE {
+            for i_dst in 0..SeedSequence::POOL_SIZE {
+                if i_src != i_dst {
+                    pool[i_dst] = mix(pool[i_dst], hashmix(pool[i_src]));
+                }
+            }
+        }
+
+        for &val in entropy.iter().skip(SeedSequence::POOL_SIZE) {
+            for slot in pool.iter_mut() {
+                *slot = mix(*slot, hashmix(val));
+            }
+        }
+
+        pool
     }
 }
@@ -27,19 +27,20 @@
 use crate::multiplier::Multiplier;
 use crate::numops::BitSize;
 use crate::outputmix::OutputMixin;
-use crate::stream::{SpecificSeqStream, Stream};
-use crate::PcgEngine;
-use num_traits::Zero;
+use crate::stream::{RestorableStream, Stream};
+use crate::{Pcg32Basic, PcgEngine, PcgError};
+use num_traits::{One, Zero};
 #[cfg(not(feature = "no_deserialize_verify"))]
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::BitAnd;
 
-impl<'de, Itype, Xtype, MulMix, OutMix> Deserialize<'de>
-    for PcgEngine<Itype, Xtype, SpecificSeqStream<Itype>, MulMix, OutMix>
+impl<'de, Itype, Xtype, StreamMix, MulMix, OutMix> Deserialize<'de>
+    for PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
 where
-    Itype: Copy + Eq + Zero + BitSize + Deserialize<'de>,
-    SpecificSeqStream<Itype>: Stream<Itype>,
+    Itype: Copy + Eq + Zero + One + BitAnd<Itype, Output = Itype> + BitSize + Deserialize<'de>,
     Xtype: BitSize,
+    StreamMix: RestorableStream<Itype>,
     MulMix: Multiplier<Itype>,
     OutMix: OutputMixin<Itype, Xtype>,
 {
@@ -78,3 +79,217 @@ where
         state.serialize(serializer)
     }
 }
+
+impl<'de> Deserialize<'de> for Pcg32Basic {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let state = crate::PCGStateInfo::deserialize(deserializer)?;
+
+        Pcg32Basic::restore_state(state).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for Pcg32Basic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.get_state().serialize(serializer)
+    }
+}
+
+/// Writes the state, increment and multiplier of a generator as fixed width
+/// hex strings instead of numbers.
+///
+/// JSON numbers are read as 64 bit floats by JavaScript and many other
+/// tools, which corrupts the 128 bit state of a `Pcg64`. Use this module on
+/// a field with `#[serde(with = "pcg_rand::serialization::hex")]`.
+///
+/// Reading accepts hex strings like `"0x2a"` as well as numbers which fit in
+/// 64 bits, so existing `Pcg32` checkpoints still load. Wider numbers reach
+/// this module as floats and are rejected instead of being rounded, so a
+/// `Pcg64` checkpoint saved as plain numbers can not be read back through it.
+/// Load such a checkpoint with the plain `Deserialize` of the generator and
+/// save it again with this module. This needs a self describing format such
+/// as JSON.
+///
+/// ```
+/// extern crate pcg_rand;
+/// extern crate rand;
+/// extern crate serde;
+/// extern crate serde_json;
+///
+/// use pcg_rand::Pcg64;
+/// use rand::SeedableRng;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Checkpoint {
+///     step: u64,
+///     #[serde(with = "pcg_rand::serialization::hex")]
+///     rng: Pcg64,
+/// }
+///
+/// # fn main() {
+/// let saved = Checkpoint { step: 7, rng: Pcg64::from_entropy() };
+/// let text = serde_json::to_string(&saved).unwrap();
+/// assert!(text.contains(r#""state":"0x"#));
+/// let loaded: Checkpoint = serde_json::from_str(&text).unwrap();
+/// # }
+/// ```
+pub mod hex {
+    use super::*;
+    use core::convert::TryFrom;
+    use core::fmt;
+    use core::mem::size_of;
+    use num_traits::Num;
+    use serde::de::{self, Visitor};
+    use std::marker::PhantomData;
+
+    /// Integers which can be written as fixed width hex strings.
+    pub trait HexInt: Num + Copy + fmt::LowerHex + TryFrom<u64> + TryFrom<u128> {}
+
+    impl<T: Num + Copy + fmt::LowerHex + TryFrom<u64> + TryFrom<u128>> HexInt for T {}
+
+    /// Generators which can be saved and restored through this module.
+    pub trait HexState: Sized {
+        type Itype: HexInt;
+
+        fn to_state(&self) -> crate::PCGStateInfo<Self::Itype>;
+        fn from_state(state: crate::PCGStateInfo<Self::Itype>) -> Result<Self, PcgError>;
+    }
+
+    impl<Itype, Xtype, StreamMix, MulMix, OutMix> HexState
+        for PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+    where
+        Itype: Copy + Eq + Zero + One + BitAnd<Itype, Output = Itype> + BitSize + HexInt,
+        Xtype: BitSize,
+        StreamMix: RestorableStream<Itype>,
+        MulMix: Multiplier<Itype>,
+        OutMix: OutputMixin<Itype, Xtype>,
+    {
+        type Itype = Itype;
+
+        fn to_state(&self) -> crate::PCGStateInfo<Itype> {
+            self.get_state()
+        }
+
+        fn from_state(state: crate::PCGStateInfo<Itype>) -> Result<Self, PcgError> {
+            #[cfg(feature = "no_deserialize_verify")]
+            {
+                Ok(Self::restore_state_with_no_verification(state))
+            }
+            #[cfg(not(feature = "no_deserialize_verify"))]
+            {
+                Self::restore_state(state)
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct HexStateOut<'a> {
+        state: String,
+        increment: String,
+        multiplier: String,
+        internal_width: usize,
+        output_width: usize,
+        output_mixin: &'a str,
+        stream: &'a str,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(bound = "Itype: HexInt")]
+    struct HexStateIn<Itype> {
+        #[serde(deserialize_with = "int_from_either")]
+        state: Itype,
+        #[serde(deserialize_with = "int_from_either")]
+        increment: Itype,
+        #[serde(deserialize_with = "int_from_either")]
+        multiplier: Itype,
+        internal_width: usize,
+        output_width: usize,
+        output_mixin: String,
+        #[serde(default)]
+        stream: String,
+    }
+
+    fn to_hex<T: HexInt>(val: T) -> String {
+        format!("{:#0width$x}", val, width = 2 + 2 * size_of::<T>())
+    }
+
+    pub fn serialize<T, S>(pcg: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: HexState,
+        S: Serializer,
+    {
+        let state = pcg.to_state();
+        HexStateOut {
+            state: to_hex(state.state),
+            increment: to_hex(state.increment),
+            multiplier: to_hex(state.multiplier),
+            internal_width: state.internal_width,
+            output_width: state.output_width,
+            output_mixin: &state.output_mixin,
+            stream: &state.stream,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: HexState,
+        D: Deserializer<'de>,
+    {
+        let state = HexStateIn::<T::Itype>::deserialize(deserializer)?;
+        T::from_state(crate::PCGStateInfo {
+            state: state.state,
+            increment: state.increment,
+            multiplier: state.multiplier,
+            internal_width: state.internal_width,
+            output_width: state.output_width,
+            output_mixin: state.output_mixin,
+            stream: state.stream,
+        })
+        .map_err(de::Error::custom)
+    }
+
+    fn int_from_either<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: HexInt,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(IntVisitor(PhantomData))
+    }
+
+    struct IntVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: HexInt> Visitor<'de> for IntVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an integer or a hex string starting with 0x")
+        }
+
+        fn visit_u64<E: de::Error>(self, val: u64) -> Result<T, E> {
+            T::try_from(val).map_err(|_| E::custom("integer is too wide for the state"))
+        }
+
+        fn visit_u128<E: de::Error>(self, val: u128) -> Result<T, E> {
+            T::try_from(val).map_err(|_| E::custom("integer is too wide for the state"))
+        }
+
+        fn visit_f64<E: de::Error>(self, _: f64) -> Result<T, E> {
+            Err(E::custom(
+                "integer does not fit in 64 bits, store it as a hex string",
+            ))
+        }
+
+        fn visit_str<E: de::Error>(self, val: &str) -> Result<T, E> {
+            val.strip_prefix("0x")
+                .and_then(|hex| T::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(val), &self))
+        }
+    }
+}
@@ -24,6 +24,11 @@
  *     http://www.pcg-random.org
  */
 
+#[cfg(target_has_atomic = "ptr")]
+use core::convert::TryFrom;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+use error::PcgError;
 use num_traits::{FromPrimitive, One, Zero};
 
 /// A stream provides the increment to the LCG. This increment should be
@@ -33,18 +38,87 @@ pub trait Stream<Itype> {
     const SERIALIZER_ID: &'static str;
     fn build(seed: Option<Itype>) -> Self;
 
-    fn set_stream(&mut self, _stream_seq: Itype) {
-        panic!("Stream setting unimplemented for this stream type");
+    /// Rebuilds a stream from the increment it reported through
+    /// `increment`, this is used when restoring a saved generator.
+    fn from_increment(increment: Itype) -> Self
+    where
+        Self: Sized,
+    {
+        Self::build(Some(increment))
     }
 
     fn increment(&self) -> Itype;
 
     fn get_stream(&self) -> Itype;
+
+    /// Rebuilds a stream from the index it reported through `get_stream`,
+    /// this is used when rebuilding a generator from its provenance.
+    fn from_stream(stream: Itype) -> Self
+    where
+        Self: Sized,
+    {
+        Self::build(Some(stream))
+    }
+}
+
+/// A stream whose increment is a constant, which lets generators using it
+/// step in `const` code.
+pub trait ConstStream<Itype>: Stream<Itype> + Sized {
+    const INCREMENT: Itype;
+    const STREAM: Self;
 }
 
+/// A stream which can be changed after the generator has been built.
+///
+/// Only generators with a settable stream provide `set_stream`, for every
+/// other stream it is a compile error.
+///
+/// ```compile_fail
+/// extern crate pcg_rand;
+/// extern crate rand;
+///
+/// use pcg_rand::Pcg32Oneseq;
+/// use rand::SeedableRng;
+///
+/// let mut pcg = Pcg32Oneseq::from_entropy();
+/// pcg.set_stream(7);
+/// ```
+pub trait SettableStream<Itype>: Stream<Itype> {
+    fn set_stream(&mut self, stream_seq: Itype);
+}
+
+/// A stream which can be rebuilt from the increment recorded when the
+/// generator was saved. Every way of restoring a saved generator, like
+/// `restore_state` and `Deserialize`, is available for these streams.
+pub trait RestorableStream<Itype>: Stream<Itype> + Sized {
+    /// Rebuilds the stream from a recorded increment, or fails if this kind
+    /// of stream can not have that increment.
+    fn restore(increment: Itype) -> Result<Self, PcgError>;
+}
+
+/// A stream which stays the same when the generator is cloned or moved.
+///
+/// Every stream except `UniqueSeqStream` is one. Functions which hand out
+/// copies of a generator, like `split_into` and `leapfrog`, need this
+/// because a copy of a generator with a `UniqueSeqStream` is on a different
+/// stream. Such a generator can be converted with `to_setseq` first.
+///
+/// ```compile_fail
+/// extern crate pcg_rand;
+/// extern crate rand;
+///
+/// use pcg_rand::Pcg32Unique;
+/// use rand::SeedableRng;
+///
+/// let pcg = Pcg32Unique::from_entropy();
+/// let split = pcg.split_into(4);
+/// ```
+pub trait StableStream<Itype>: Stream<Itype> + Clone {}
+
 /// This sequence stream defines constants as provided by the PCG paper.
 /// This struct is implemented with a macro to provide values for each
 /// Stream<Itype>.
+#[derive(Clone)]
 pub struct OneSeqStream;
 
 macro_rules! make_one_seq {
@@ -63,6 +137,22 @@ macro_rules! make_one_seq {
             fn get_stream(&self) -> $t {
                 $e
             }
+        }
+
+        impl ConstStream<$t> for OneSeqStream {
+            const INCREMENT: $t = $e;
+            const STREAM: Self = OneSeqStream;
+        }
+
+        impl StableStream<$t> for OneSeqStream {}
+
+        impl RestorableStream<$t> for OneSeqStream {
+            fn restore(increment: $t) -> Result<Self, PcgError> {
+                if increment != $e {
+                    return Err(PcgError::IncrementMismatch);
+                }
+                Ok(OneSeqStream)
+            }
         })*
 	}
 }
@@ -76,6 +166,7 @@ make_one_seq! {
 /// This stream provides an increment of 0 to the LCG. This turns the
 /// LCG into a MCG, which while being less statistically sound than an LCG,
 /// it is faster.
+#[derive(Clone)]
 pub struct NoSeqStream;
 
 macro_rules! make_no_seq {
@@ -94,6 +185,22 @@ macro_rules! make_no_seq {
             fn get_stream(&self) -> $t {
                 $e
             }
+        }
+
+        impl ConstStream<$t> for NoSeqStream {
+            const INCREMENT: $t = $e;
+            const STREAM: Self = NoSeqStream;
+        }
+
+        impl StableStream<$t> for NoSeqStream {}
+
+        impl RestorableStream<$t> for NoSeqStream {
+            fn restore(increment: $t) -> Result<Self, PcgError> {
+                if increment != $e {
+                    return Err(PcgError::IncrementMismatch);
+                }
+                Ok(NoSeqStream)
+            }
         })*
 	}
 }
@@ -107,8 +214,14 @@ make_no_seq! {
 /// By default this stream provides the same stream as OneSeqStream. The
 /// advantage to this stream is it can be changed at runtime. This incurs an
 /// extra Itype of storage overhead.
+///
+/// Like the C++ implementation the increment for stream `s` is
+/// `(s << 1) | 1`, so every stream index selects a distinct sequence and
+/// `get_stream` returns the index that was set. The top bit of the index is
+/// lost in the shift.
+#[derive(Clone)]
 pub struct SpecificSeqStream<Itype> {
-    inc: Itype,
+    pub(crate) inc: Itype,
 }
 
 impl<Itype> SpecificSeqStream<Itype>
@@ -130,14 +243,101 @@ macro_rules! make_set_seq {
                                 inc : $e,
                             },
                     Some(seed) => SpecificSeqStream {
-                        inc: seed | $t::one(),
+                        inc: (seed << 1) | $t::one(),
                     },
                 }
 
             }
 
+            fn from_increment(increment: $t) -> Self {
+                SpecificSeqStream { inc: increment }
+            }
+
+            #[inline(always)]
+            fn increment(&self) -> $t {
+                self.inc
+            }
+
+            fn get_stream(&self) -> $t {
+                self.inc >> 1
+            }
+        }
+
+        impl SpecificSeqStream<$t> {
+            /// Builds the stream with index `stream`, this is the same as
+            /// `build(Some(stream))` but can be used in `const` code.
+            pub const fn with_stream(stream: $t) -> Self {
+                SpecificSeqStream {
+                    inc: (stream << 1) | 1,
+                }
+            }
+        }
+
+        impl SettableStream<$t> for SpecificSeqStream<$t> {
             fn set_stream(&mut self, stream_seq : $t) {
-                self.inc = stream_seq | $t::one();
+                self.inc = (stream_seq << 1) | $t::one();
+            }
+        }
+
+        impl StableStream<$t> for SpecificSeqStream<$t> {}
+
+        impl RestorableStream<$t> for SpecificSeqStream<$t> {
+            fn restore(increment: $t) -> Result<Self, PcgError> {
+                Ok(SpecificSeqStream::from_increment(increment))
+            }
+        })*
+    }
+}
+
+make_set_seq! {
+    u32 => 2_891_336_453u32;
+    u64 => 1_442_695_040_888_963_407u64;
+    u128 => 117_397_592_171_526_113_268_558_934_119_004_209_487u128 //u128::from_parts(6364136223846793005,1442695040888963407)
+}
+
+/// The settable stream as it behaved up to version 0.13, the stream index is
+/// used as the increment with the low bit forced on. This means streams `2k`
+/// and `2k + 1` produce the same sequence, it is only provided so outputs of
+/// older versions can still be reproduced. Its states are saved with the id
+/// `LegacySetSeq` so they are not restored into a `SpecificSeqStream`.
+///
+/// ```
+/// extern crate pcg_rand;
+/// extern crate rand;
+///
+/// use pcg_rand::{PcgEngine, seeds::PcgSeeder, stream::LegacySeqStream};
+/// use pcg_rand::{multiplier::DefaultMultiplier, outputmix::XshRrMixin};
+/// use rand::{RngCore, SeedableRng};
+///
+/// type LegacyPcg32 = PcgEngine<u64, u32, LegacySeqStream<u64>, DefaultMultiplier, XshRrMixin>;
+///
+/// let mut ra = LegacyPcg32::from_seed(PcgSeeder::seed_with_stream(42, 6));
+/// let mut rb = LegacyPcg32::from_seed(PcgSeeder::seed_with_stream(42, 7));
+///
+/// assert_eq!(ra.next_u32(), rb.next_u32());
+/// ```
+#[derive(Clone)]
+pub struct LegacySeqStream<Itype> {
+    inc: Itype,
+}
+
+macro_rules! make_legacy_seq {
+    ( $( $t:ident => $e:expr);* ) => {
+        $(impl Stream<$t> for LegacySeqStream<$t> {
+            const SERIALIZER_ID: &'static str = "LegacySetSeq";
+            fn build(seed: Option<$t>) -> Self {
+                match seed {
+                    None => LegacySeqStream {
+                                inc : $e,
+                            },
+                    Some(seed) => LegacySeqStream {
+                        inc: seed | $t::one(),
+                    },
+                }
+            }
+
+            fn from_increment(increment: $t) -> Self {
+                LegacySeqStream { inc: increment }
             }
 
             #[inline(always)]
@@ -148,11 +348,25 @@ macro_rules! make_set_seq {
             fn get_stream(&self) -> $t {
                 self.inc
             }
+        }
+
+        impl SettableStream<$t> for LegacySeqStream<$t> {
+            fn set_stream(&mut self, stream_seq : $t) {
+                self.inc = stream_seq | $t::one();
+            }
+        }
+
+        impl StableStream<$t> for LegacySeqStream<$t> {}
+
+        impl RestorableStream<$t> for LegacySeqStream<$t> {
+            fn restore(increment: $t) -> Result<Self, PcgError> {
+                Ok(LegacySeqStream::from_increment(increment))
+            }
         })*
     }
 }
 
-make_set_seq! {
+make_legacy_seq! {
     u32 => 2_891_336_453u32;
     u64 => 1_442_695_040_888_963_407u64;
     u128 => 117_397_592_171_526_113_268_558_934_119_004_209_487u128 //u128::from_parts(6364136223846793005,1442695040888963407)
@@ -162,6 +376,7 @@ make_set_seq! {
 /// generator in memory. This means that two PCG with the same seed
 /// can produce different sequences of numbers. Though if the generator is
 /// moved it will change the stream.
+#[derive(Clone)]
 pub struct UniqueSeqStream;
 
 impl<Itype> Stream<Itype> for UniqueSeqStream
@@ -182,3 +397,74 @@ where
         Itype::from_usize(self as *const UniqueSeqStream as usize | 1).unwrap()
     }
 }
+
+#[cfg(target_has_atomic = "ptr")]
+static NEXT_COUNTED_STREAM: AtomicUsize = AtomicUsize::new(0);
+
+/// This stream gives every generator its own stream like `UniqueSeqStream`,
+/// but the stream is taken from a process wide counter when the generator is
+/// built and then stored. Moving the generator does not change its stream
+/// and it can be saved and restored like a `SpecificSeqStream`. Cloning a
+/// generator keeps the stream, so the clone produces the same sequence.
+///
+/// The counter starts from zero in every process, so the streams are unique
+/// within a process but are not random. Restoring a generator moves the
+/// counter past its stream, building a generator panics once the counter
+/// has no streams left. This stream needs atomic operations
+/// so it is missing on targets which do not have them.
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Clone)]
+pub struct CountedSeqStream<Itype> {
+    inc: Itype,
+}
+
+macro_rules! make_counted_seq {
+    ( $( $t:ident ),* ) => {
+        $(impl Stream<$t> for CountedSeqStream<$t> {
+            const SERIALIZER_ID: &'static str = "Counted";
+            fn build(_: Option<$t>) -> Self {
+                let id = NEXT_COUNTED_STREAM
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+                    .expect("every counted stream has been handed out");
+                CountedSeqStream {
+                    inc: ((id as $t) << 1) | $t::one(),
+                }
+            }
+
+            fn from_increment(increment: $t) -> Self {
+                //Make sure a restored stream is never handed out again, the
+                //counter can not reach ids which do not fit in a usize
+                if let Ok(id) = usize::try_from(increment >> 1) {
+                    NEXT_COUNTED_STREAM.fetch_max(id.saturating_add(1), Ordering::Relaxed);
+                }
+                CountedSeqStream { inc: increment }
+            }
+
+            #[inline(always)]
+            fn increment(&self) -> $t {
+                self.inc
+            }
+
+            fn get_stream(&self) -> $t {
+                self.inc >> 1
+            }
+
+            // Building would take a new stream from the counter
+            fn from_stream(stream: $t) -> Self {
+                CountedSeqStream::from_increment((stream << 1) | $t::one())
+            }
+        }
+
+        impl StableStream<$t> for CountedSeqStream<$t> {}
+
+        // The restored generator keeps the stream it was saved with
+        impl RestorableStream<$t> for CountedSeqStream<$t> {
+            fn restore(increment: $t) -> Result<Self, PcgError> {
+                Ok(CountedSeqStream::from_increment(increment))
+            }
+        })*
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+make_counted_seq!(u32, u64, u128);
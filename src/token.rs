@@ -0,0 +1,169 @@
+/*
+ * PCG Random Number Generation for Rust
+ *
+ * Copyright 2015 John Brooks <jeb@robojeb.dev>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+// One line text tokens which fully describe a generator, for logs and bug
+// reports. A token looks like `pcg32-dxsm-setseq:state=0x…:inc=0x…`.
+//
+// The kind is named after the output width like the C++ library, `pcg32` has
+// a 64 bit state and `pcg64` a 128 bit state. Other widths are written out
+// as `pcg128x32`. The multiplier is implied by the kind.
+
+use core::fmt::{self, Write};
+use multiplier::Multiplier;
+use numops::BitSize;
+use outputmix::OutputMixin;
+use stream::Stream;
+use PcgEngine;
+
+impl<Itype, Xtype, StreamMix, MulMix, OutMix> fmt::Display
+    for PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+where
+    Itype: Copy + BitSize + fmt::LowerHex,
+    Xtype: BitSize,
+    StreamMix: Stream<Itype>,
+    MulMix: Multiplier<Itype>,
+    OutMix: OutputMixin<Itype, Xtype>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if Itype::BITS == 2 * Xtype::BITS {
+            write!(f, "pcg{}", Xtype::BITS)?;
+        } else {
+            write!(f, "pcg{}x{}", Itype::BITS, Xtype::BITS)?;
+        }
+
+        f.write_char('-')?;
+        write_lower(f, OutMix::SERIALIZER_ID)?;
+        f.write_char('-')?;
+        write_lower(f, StreamMix::SERIALIZER_ID)?;
+        write!(
+            f,
+            ":state={:#x}:inc={:#x}",
+            self.state,
+            self.stream_mix.increment()
+        )
+    }
+}
+
+// Writes an id in lower case without allocating
+fn write_lower(f: &mut fmt::Formatter, id: &str) -> fmt::Result {
+    id.chars()
+        .try_for_each(|c| f.write_char(c.to_ascii_lowercase()))
+}
+
+// Parsing needs `PCGStateInfo`, which holds the ids as a `String`
+#[cfg(feature = "std")]
+mod parse {
+    use core::str::FromStr;
+    use error::PcgError;
+    use multiplier::Multiplier;
+    use num_traits::{Num, One, Zero};
+    use numops::BitSize;
+    use outputmix::OutputMixin;
+    use stream::{RestorableStream, Stream};
+    use {PCGStateInfo, PcgEngine};
+
+    // Splits a token into a recorded state, the ids are matched without case
+    fn parse_token<Itype, Xtype, StreamMix, MulMix, OutMix>(
+        token: &str,
+    ) -> Result<PCGStateInfo<Itype>, PcgError>
+    where
+        Itype: Num,
+        StreamMix: Stream<Itype>,
+        MulMix: Multiplier<Itype>,
+        OutMix: OutputMixin<Itype, Xtype>,
+    {
+        let mut fields = token.trim().split(':');
+        let kind = fields.next().unwrap_or("");
+        let state = parse_value(fields.next(), "state=")?;
+        let increment = parse_value(fields.next(), "inc=")?;
+        if fields.next().is_some() {
+            return Err(PcgError::InvalidToken);
+        }
+
+        let kind = kind.to_ascii_lowercase();
+        let mut kind = kind.split('-');
+        let widths = kind
+            .next()
+            .and_then(|w| w.strip_prefix("pcg"))
+            .ok_or(PcgError::InvalidToken)?;
+        let output_mixin = match_id(kind.next(), OutMix::SERIALIZER_ID)?;
+        let stream = match_id(kind.next(), StreamMix::SERIALIZER_ID)?;
+        if kind.next().is_some() {
+            return Err(PcgError::InvalidToken);
+        }
+
+        let parse_width = |w: &str| w.parse::<usize>().map_err(|_| PcgError::InvalidToken);
+        let (internal_width, output_width) = match widths.split_once('x') {
+            Some((internal, output)) => (parse_width(internal)?, parse_width(output)?),
+            None => {
+                let output = parse_width(widths)?;
+                (2 * output, output)
+            }
+        };
+
+        Ok(PCGStateInfo {
+            state,
+            increment,
+            multiplier: MulMix::multiplier(),
+            internal_width,
+            output_width,
+            output_mixin,
+            stream,
+        })
+    }
+
+    fn parse_value<Itype: Num>(field: Option<&str>, name: &str) -> Result<Itype, PcgError> {
+        field
+            .and_then(|f| f.strip_prefix(name))
+            .and_then(|f| f.strip_prefix("0x"))
+            .and_then(|hex| Itype::from_str_radix(hex, 16).ok())
+            .ok_or(PcgError::InvalidToken)
+    }
+
+    // Any other id is passed on and `restore_state` reports it as a mismatch.
+    // The ids of mixins and streams outside this crate can not be listed here,
+    // so an unknown id is a `MixinMismatch` or `StreamMismatch` rather than an
+    // `InvalidToken`.
+    fn match_id(found: Option<&str>, expected: &'static str) -> Result<String, PcgError> {
+        match found {
+            Some(id) if id.eq_ignore_ascii_case(expected) => Ok(expected.into()),
+            Some(id) if !id.is_empty() => Ok(id.into()),
+            _ => Err(PcgError::InvalidToken),
+        }
+    }
+
+    impl<Itype, Xtype, StreamMix, MulMix, OutMix> FromStr
+        for PcgEngine<Itype, Xtype, StreamMix, MulMix, OutMix>
+    where
+        Itype: Copy + Eq + Zero + One + core::ops::BitAnd<Itype, Output = Itype> + BitSize + Num,
+        Xtype: BitSize,
+        StreamMix: RestorableStream<Itype>,
+        MulMix: Multiplier<Itype>,
+        OutMix: OutputMixin<Itype, Xtype>,
+    {
+        type Err = PcgError;
+
+        /// Restores a PCG from the token written by `Display` and
+        /// verifies it like `restore_state`.
+        fn from_str(token: &str) -> Result<Self, PcgError> {
+            let state = parse_token::<Itype, Xtype, StreamMix, MulMix, OutMix>(token)?;
+            Self::restore_state(state)
+        }
+    }
+}
@@ -1,10 +1,24 @@
-
 extern crate pcg_rand;
 extern crate rand;
 
-use pcg_rand::{Pcg32, Pcg64};
+use pcg_rand::lcg::LcgStep;
+use pcg_rand::multiplier::Multiplier;
+use pcg_rand::outputmix::DXsMMixin;
+use pcg_rand::stream::SpecificSeqStream;
+use pcg_rand::{Pcg32, Pcg32Fast, Pcg64, PcgEngine};
 use rand::RngCore;
 
+// The default multiplier without a precomputed jump table
+struct TablelessMultiplier;
+
+impl Multiplier<u64> for TablelessMultiplier {
+    fn multiplier() -> u64 {
+        6_364_136_223_846_793_005
+    }
+}
+
+type TablelessPcg32 = PcgEngine<u64, u32, SpecificSeqStream<u64>, TablelessMultiplier, DXsMMixin>;
+
 #[test]
 fn pcg32_short_advance() {
     // Test that advancing a short distance is equal to going the long way round
@@ -87,4 +101,187 @@ fn pcg64_backstep() {
     ra.advance(u128::MAX);
 
     assert_eq!(ra.next_u64(), rb.next_u64());
-}
\ No newline at end of file
+}
+#[test]
+fn pcg32_distance() {
+    let ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: Pcg32 = Pcg32::new_unseeded();
+
+    assert_eq!(ra.distance(&rb), 0);
+
+    for _ in 0..1234 {
+        rb.next_u32();
+    }
+
+    assert_eq!(ra.distance(&rb), 1234);
+    // Going the other way wraps all the way around the period
+    assert_eq!(rb.distance(&ra), 0u64.wrapping_sub(1234));
+}
+
+#[test]
+fn pcg32_distance_advance() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: Pcg32 = Pcg32::new_unseeded();
+
+    rb.advance(59032011);
+    assert_eq!(ra.distance(&rb), 59032011);
+
+    ra.advance(ra.distance(&rb));
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn pcg32fast_distance() {
+    let ra: Pcg32Fast = Pcg32Fast::new_unseeded();
+    let mut rb: Pcg32Fast = Pcg32Fast::new_unseeded();
+
+    rb.advance(987654);
+    assert_eq!(ra.distance(&rb), 987654);
+}
+
+#[test]
+fn pcg64_distance() {
+    let ra: Pcg64 = Pcg64::new_unseeded();
+    let mut rb: Pcg64 = Pcg64::new_unseeded();
+
+    rb.advance(0xDEAD_BEEF_0123_4567_89AB_CDEF);
+    assert_eq!(ra.distance(&rb), 0xDEAD_BEEF_0123_4567_89AB_CDEF);
+}
+
+#[test]
+fn pcg32_step_back() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: Pcg32 = Pcg32::new_unseeded();
+
+    ra.next_u32();
+    ra.step_back();
+
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn pcg64_step_back() {
+    let mut ra: Pcg64 = Pcg64::new_unseeded();
+    let mut rb: Pcg64 = Pcg64::new_unseeded();
+
+    ra.next_u64();
+    ra.step_back();
+
+    assert_eq!(ra.next_u64(), rb.next_u64());
+}
+
+#[test]
+fn pcg32fast_step_back() {
+    let mut ra: Pcg32Fast = Pcg32Fast::new_unseeded();
+    let mut rb: Pcg32Fast = Pcg32Fast::new_unseeded();
+
+    ra.next_u32();
+    ra.step_back();
+
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn pcg32_retreat() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: Pcg32 = Pcg32::new_unseeded();
+
+    ra.advance(59032011);
+    ra.retreat(59032000);
+    rb.advance(11);
+
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn pcg32_reverse_outputs() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+
+    let forward: Vec<u32> = ra.outputs().take(100).collect();
+    let mut outputs = ra.outputs();
+    let mut backward: Vec<u32> = (0..100).map(|_| outputs.step_back()).collect();
+    backward.reverse();
+
+    assert_eq!(forward, backward);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_peek() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+
+    let peeked = ra.peek_n(10);
+    assert_eq!(ra.peek(), peeked[0]);
+    assert_eq!(ra.outputs().take(10).collect::<Vec<_>>(), peeked);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_output_at() {
+    let ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: Pcg32 = Pcg32::new_unseeded();
+
+    let indices: Vec<u64> = vec![0, 1, 2, 10, 300, 59032011, 7];
+    let expected: Vec<u32> = indices
+        .iter()
+        .map(|&i| {
+            let mut rc: Pcg32 = Pcg32::new_unseeded();
+            rc.advance(i);
+            rc.next_u32()
+        })
+        .collect();
+
+    for (&i, &e) in indices.iter().zip(expected.iter()) {
+        assert_eq!(ra.output_at(i), e);
+    }
+    assert_eq!(ra.outputs_at(&indices), expected);
+
+    // The generator itself does not move
+    assert_eq!(ra.output_at(0), rb.next_u32());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg64_output_at() {
+    let ra: Pcg64 = Pcg64::new_unseeded();
+    let mut rb: Pcg64 = Pcg64::new_unseeded();
+
+    rb.advance(u128::MAX - 5);
+    assert_eq!(ra.output_at(u128::MAX - 5), rb.next_u64());
+    assert_eq!(
+        ra.outputs_at(&[u128::MAX - 5, 0]),
+        vec![ra.output_at(u128::MAX - 5), ra.peek()]
+    );
+}
+
+#[test]
+fn pcg32_tableless_advance() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: TablelessPcg32 = TablelessPcg32::new_unseeded();
+
+    ra.advance(0xDEAD_BEEF_0123_4567);
+    rb.advance(0xDEAD_BEEF_0123_4567);
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    #[cfg(feature = "std")]
+    assert_eq!(ra.outputs_at(&[3, 59032011]), rb.outputs_at(&[3, 59032011]));
+}
+
+#[test]
+fn pcg32_lcg_step_algebra() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut rb: Pcg32 = Pcg32::new_unseeded();
+
+    let step = ra.lcg_step();
+    assert_eq!(step.pow(1000), ra.jump_step(1000));
+    assert_eq!(step.pow(10).compose(&step.pow(5)), step.pow(15));
+    assert_eq!(step.compose(&step.inverse()), LcgStep::identity());
+
+    ra.apply_step(&step.pow(1000));
+    rb.advance(1000);
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    ra.apply_step(&ra.jump_step(77).inverse());
+    rb.retreat(77);
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "std")]
+
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::binary::{MAGIC, VERSION};
+use pcg_rand::extension::{Ext2, Ext4, Pcg32Ext};
+use pcg_rand::multiplier::DefaultMultiplier;
+use pcg_rand::outputmix::OutputMixin;
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::stream::SpecificSeqStream;
+use pcg_rand::{Pcg32, Pcg32Fast, Pcg32Oneseq, Pcg64, PcgEngine, PcgError};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn binary_roundtrip() {
+    let mut ra = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    ra.advance(1000);
+    let bytes = ra.to_bytes().unwrap();
+    assert_eq!(bytes.len(), 56);
+    assert_eq!(bytes[..4], MAGIC);
+    assert_eq!(bytes[4..6], VERSION.to_le_bytes());
+
+    let mut rb = Pcg32::from_bytes(&bytes).unwrap();
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+
+    let mut ra = Pcg64::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    let mut rb = Pcg64::from_bytes(&ra.to_bytes().unwrap()).unwrap();
+    assert_eq!(ra.next_u64(), rb.next_u64());
+
+    let mut ra = Pcg32Oneseq::from_seed(PcgSeeder::seed(42));
+    let mut rb = Pcg32Oneseq::from_bytes(&ra.to_bytes().unwrap()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut ra = Pcg32Fast::from_seed(PcgSeeder::seed(42));
+    let mut rb = Pcg32Fast::from_bytes(&ra.to_bytes().unwrap()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn binary_ext_roundtrip() {
+    let mut ra: Pcg32Ext<Ext4> = Pcg32Ext::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    for _ in 0..1000 {
+        ra.next_u32();
+    }
+    let bytes = ra.to_bytes().unwrap();
+
+    let mut rb = Pcg32Ext::<Ext4>::from_bytes(&bytes).unwrap();
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+
+    assert_eq!(
+        Pcg32Ext::<Ext2>::from_bytes(&bytes).err(),
+        Some(PcgError::ExtSizeMismatch {
+            expected: 2,
+            found: 4
+        })
+    );
+}
+
+#[test]
+fn binary_wrong_kind() {
+    let bytes = Pcg32::from_seed(PcgSeeder::seed(42)).to_bytes().unwrap();
+    assert_eq!(
+        Pcg32Oneseq::from_bytes(&bytes).err(),
+        Some(PcgError::StreamMismatch {
+            expected: "OneSeq",
+            found: "SetSeq".into()
+        })
+    );
+    assert_eq!(
+        Pcg64::from_bytes(&bytes).err(),
+        Some(PcgError::WidthMismatch {
+            expected: (128, 64),
+            found: (64, 32)
+        })
+    );
+}
+
+#[test]
+fn binary_damaged() {
+    let bytes = Pcg32::from_seed(PcgSeeder::seed(42)).to_bytes().unwrap();
+
+    let mut bad = bytes.clone();
+    bad[0] = b'X';
+    assert_eq!(Pcg32::from_bytes(&bad).err(), Some(PcgError::BadMagic));
+
+    let mut bad = bytes.clone();
+    bad[4] = 9;
+    assert_eq!(
+        Pcg32::from_bytes(&bad).err(),
+        Some(PcgError::UnsupportedVersion { found: 9 })
+    );
+
+    let mut bad = bytes.clone();
+    bad[30] ^= 1;
+    assert_eq!(
+        Pcg32::from_bytes(&bad).err(),
+        Some(PcgError::ChecksumMismatch)
+    );
+
+    assert_eq!(
+        Pcg32::from_bytes(&bytes[..10]).err(),
+        Some(PcgError::LengthMismatch {
+            expected: 14,
+            found: 10
+        })
+    );
+}
+
+// An output mixin whose id does not fit in the one byte length
+struct LongIdMixin;
+
+impl OutputMixin<u64, u32> for LongIdMixin {
+    const SERIALIZER_ID: &'static str = concat!(
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    );
+    fn output(state: u64, _increment: u64, _multiplier: u64) -> u32 {
+        (state >> 32) as u32
+    }
+}
+
+#[test]
+fn binary_id_too_long() {
+    type LongIdPcg = PcgEngine<u64, u32, SpecificSeqStream<u64>, DefaultMultiplier, LongIdMixin>;
+
+    let ra = LongIdPcg::from_seed(PcgSeeder::seed(42));
+    assert_eq!(
+        ra.to_bytes(),
+        Err(PcgError::FieldTooLarge {
+            field: "output mixin id",
+            value: 256
+        })
+    );
+}
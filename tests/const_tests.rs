@@ -0,0 +1,203 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::multiplier::{DefaultMultiplier, McgMultiplier, Multiplier};
+use pcg_rand::outputmix::{DXsMMixin, OutputMixin, XshRrMixin, XshRsMixin};
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{
+    McgDXsM6432, OneseqXshRr6432, Pcg32, Pcg32Basic, Pcg32Fast, Pcg32Oneseq, SetseqXshRr6432,
+    SetseqXshRs6432,
+};
+use rand::{thread_rng, Rng, RngCore, SeedableRng};
+
+const RNG: Pcg32 = Pcg32::const_new(42, 54);
+const FAST: Pcg32Fast = Pcg32Fast::const_new(43);
+
+const fn make_table() -> [u32; 16] {
+    let mut rng = RNG;
+    let mut table = [0; 16];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = rng.const_output();
+        rng = rng.const_step();
+        i += 1;
+    }
+    table
+}
+
+static TABLE: [u32; 16] = make_table();
+
+macro_rules! check_const {
+    ($name:ident, $t:ty, $new:expr, $seed:expr, $next:ident) => {
+        #[test]
+        fn $name() {
+            let mut ra: $t = $new;
+            let mut rb: $t = SeedableRng::from_seed($seed);
+            for _ in 0..100 {
+                assert_eq!(ra.const_output(), rb.$next());
+                ra = ra.const_step();
+            }
+
+            let ra = ra.const_advance(12_345);
+            rb.advance(12_345);
+            assert_eq!(ra.const_output(), rb.$next());
+        }
+    };
+}
+
+check_const!(
+    pcg32_const,
+    Pcg32,
+    Pcg32::const_new(42, 54),
+    PcgSeeder::seed_with_stream(42, 54),
+    next_u32
+);
+check_const!(
+    setseq_xshrs_const,
+    SetseqXshRs6432,
+    SetseqXshRs6432::const_new(42, 54),
+    PcgSeeder::seed_with_stream(42, 54),
+    next_u32
+);
+check_const!(
+    setseq_xshrr_const,
+    SetseqXshRr6432,
+    SetseqXshRr6432::const_new(42, 54),
+    PcgSeeder::seed_with_stream(42, 54),
+    next_u32
+);
+check_const!(
+    oneseq_const,
+    Pcg32Oneseq,
+    Pcg32Oneseq::const_new(42),
+    PcgSeeder::seed(42),
+    next_u32
+);
+check_const!(
+    oneseq_xshrr_const,
+    OneseqXshRr6432,
+    OneseqXshRr6432::const_new(42),
+    PcgSeeder::seed(42),
+    next_u32
+);
+check_const!(mcg_const, Pcg32Fast, FAST, PcgSeeder::seed(43), next_u32);
+check_const!(
+    mcg_dxsm_const,
+    McgDXsM6432,
+    McgDXsM6432::const_new(43),
+    PcgSeeder::seed(43),
+    next_u32
+);
+
+#[cfg(feature = "u128")]
+check_const!(
+    pcg32l_const,
+    pcg_rand::Pcg32L,
+    pcg_rand::Pcg32L::const_new(42, 54),
+    PcgSeeder::seed_with_stream(42, 54),
+    next_u32
+);
+#[cfg(feature = "u128")]
+check_const!(
+    pcg64_const,
+    pcg_rand::Pcg64,
+    pcg_rand::Pcg64::const_new(42, 54),
+    PcgSeeder::seed_with_stream(42, 54),
+    next_u64
+);
+#[cfg(feature = "u128")]
+check_const!(
+    pcg64_fast_const,
+    pcg_rand::Pcg64Fast,
+    pcg_rand::Pcg64Fast::const_new(43),
+    PcgSeeder::seed(43),
+    next_u64
+);
+
+// The const outputs are written separately from the generic ones, so check
+// every mixin and width against each other directly
+macro_rules! check_const_output {
+    ($name:ident, $i:ty => $x:ty, $const_out:ident) => {
+        #[test]
+        fn $name() {
+            let mults = [
+                <DefaultMultiplier as Multiplier<$i>>::multiplier(),
+                <McgMultiplier as Multiplier<$i>>::multiplier(),
+            ];
+            let mut states = vec![0, 1, <$i>::MAX];
+            states.extend((0..1000).map(|_| thread_rng().gen::<$i>()));
+
+            for &mult in mults.iter() {
+                for &state in states.iter() {
+                    assert_eq!(
+                        XshRsMixin::$const_out(state, mult),
+                        <XshRsMixin as OutputMixin<$i, $x>>::output(state, 1, mult)
+                    );
+                    assert_eq!(
+                        XshRrMixin::$const_out(state, mult),
+                        <XshRrMixin as OutputMixin<$i, $x>>::output(state, 1, mult)
+                    );
+                    assert_eq!(
+                        DXsMMixin::$const_out(state, mult),
+                        <DXsMMixin as OutputMixin<$i, $x>>::output(state, 1, mult)
+                    );
+                }
+            }
+        }
+    };
+}
+
+check_const_output!(output_64_32_const, u64 => u32, output_u64_u32);
+#[cfg(feature = "u128")]
+check_const_output!(output_128_32_const, u128 => u32, output_u128_u32);
+#[cfg(feature = "u128")]
+check_const_output!(output_128_64_const, u128 => u64, output_u128_u64);
+
+#[test]
+fn const_advance_matches() {
+    for &delta in &[0, 1, 2, 12_345, 1 << 40, u64::MAX - 1, u64::MAX] {
+        let mut ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 54));
+        ra.advance(delta);
+        let rb = Pcg32::const_new(42, 54).const_advance(delta);
+        assert_eq!(rb.const_output(), ra.next_u32());
+
+        let mut ra: Pcg32Fast = Pcg32Fast::from_seed(PcgSeeder::seed(43));
+        ra.advance(delta);
+        let rb = Pcg32Fast::const_new(43).const_advance(delta);
+        assert_eq!(rb.const_output(), ra.next_u32());
+    }
+}
+
+#[cfg(feature = "u128")]
+#[test]
+fn const_advance_matches_u128() {
+    for &delta in &[0, 1, 12_345, 1 << 100, u128::MAX - 1, u128::MAX] {
+        let mut ra = pcg_rand::Pcg64::from_seed(PcgSeeder::seed_with_stream(42, 54));
+        ra.advance(delta);
+        let rb = pcg_rand::Pcg64::const_new(42, 54).const_advance(delta);
+        assert_eq!(rb.const_output(), ra.next_u64());
+
+        let mut ra = pcg_rand::Pcg64Fast::from_seed(PcgSeeder::seed(43));
+        ra.advance(delta);
+        let rb = pcg_rand::Pcg64Fast::const_new(43).const_advance(delta);
+        assert_eq!(rb.const_output(), ra.next_u64());
+    }
+}
+
+#[test]
+fn const_table() {
+    let mut rng: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    for &val in TABLE.iter() {
+        assert_eq!(val, rng.next_u32());
+    }
+}
+
+#[test]
+fn pcg32basic_const() {
+    let mut ra = Pcg32Basic::const_new(42, 54);
+    let mut rb = Pcg32Basic::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    for _ in 0..100 {
+        assert_eq!(ra.const_output(), rb.next_u32());
+        ra = ra.const_step();
+    }
+}
@@ -0,0 +1,58 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32Fast, Pcg32Oneseq, Pcg32Unique};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn oneseq_to_setseq() {
+    let mut ra: Pcg32Oneseq = Pcg32Oneseq::from_seed(PcgSeeder::seed(42));
+    ra.advance(1000);
+    let mut rb = ra.to_setseq();
+    assert_eq!(rb.position(), 1000);
+
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+}
+
+#[test]
+fn unique_to_setseq() {
+    let mut ra: Pcg32Unique = Pcg32Unique::from_seed(PcgSeeder::seed(42));
+    ra.advance(1000);
+    let step = ra.lcg_step();
+    let first = ra.peek();
+
+    //The converted PCG keeps the stream of the place it was converted in
+    let rb = Box::new(ra.to_setseq());
+    assert_eq!(rb.lcg_step(), step);
+    assert_eq!(rb.peek(), first);
+    assert_eq!(rb.position(), 1000);
+}
+
+#[test]
+fn mcg_to_setseq() {
+    let mut ra: Pcg32Fast = Pcg32Fast::from_seed(PcgSeeder::seed(43));
+    ra.advance(1000);
+    let mut rb = ra.to_setseq();
+    assert_eq!(rb.position(), 1000);
+
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn converted_restore() {
+    let mut ra: Pcg32Oneseq = Pcg32Oneseq::from_seed(PcgSeeder::seed(42));
+    ra.advance(1000);
+
+    let saved = ra.to_setseq().get_state();
+    let mut rb = pcg_rand::Pcg32::restore_state(saved).unwrap();
+
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+}
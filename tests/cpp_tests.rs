@@ -0,0 +1,78 @@
+#![cfg(feature = "std")]
+
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32, Pcg32Fast, Pcg64, PcgError, SetseqXshRr6432};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn cpp_import_pcg32() {
+    //What `std::cout << pcg32(42, 54)` prints with the C++ library
+    let text = "6364136223846793005 109 1753877967969059832";
+    let mut rng = SetseqXshRr6432::from_cpp_text(text).unwrap();
+
+    //The first outputs of the pcg32 demo program for seed 42 and stream 54
+    assert_eq!(rng.next_u32(), 0xa15c_02b7);
+    assert_eq!(rng.next_u32(), 0x7b47_f409);
+    assert_eq!(rng.next_u32(), 0xba1d_3330);
+
+    assert_eq!(
+        SetseqXshRr6432::from_cpp_text(text).unwrap().to_cpp_text(),
+        text
+    );
+}
+
+#[test]
+fn cpp_roundtrip() {
+    let mut ra = Pcg32::from_entropy();
+    let mut rb = Pcg32::from_cpp_text(&ra.to_cpp_text()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut ra = Pcg32Fast::from_entropy();
+    let mut rb = Pcg32Fast::from_cpp_text(&ra.to_cpp_text()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    //The 128 bit C++ engines keep the state from before the last step
+    let ra = Pcg64::from_seed(PcgSeeder::seed_with_stream(5, 3));
+    let text = ra.to_cpp_text();
+    let fields: Vec<u128> = text.split(' ').map(|f| f.parse().unwrap()).collect();
+    assert_eq!(ra.lcg_step().apply(fields[2]), ra.provenance().seed);
+
+    let mut ra = Pcg64::from_entropy();
+    let mut rb = Pcg64::from_cpp_text(&ra.to_cpp_text()).unwrap();
+    assert_eq!(ra.next_u64(), rb.next_u64());
+}
+
+#[test]
+fn cpp_errors() {
+    assert_eq!(
+        Pcg32::from_cpp_text("12605985483714917081 109 1").err(),
+        Some(PcgError::MultiplierMismatch)
+    );
+    assert_eq!(
+        Pcg32Fast::from_cpp_text("12605985483714917081 109 1").err(),
+        Some(PcgError::IncrementMismatch)
+    );
+    assert_eq!(
+        Pcg32::from_cpp_text("6364136223846793005 108 1").err(),
+        Some(PcgError::EvenIncrement)
+    );
+
+    for bad in &[
+        "",
+        "6364136223846793005 109",
+        "6364136223846793005 109 1 1",
+        "6364136223846793005 0x6d 1",
+        "6364136223846793005 109 -1",
+        "6364136223846793005 109 18446744073709551616",
+    ] {
+        assert_eq!(
+            Pcg32::from_cpp_text(bad).err(),
+            Some(PcgError::InvalidCppText),
+            "{}",
+            bad
+        );
+    }
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "std")]
+
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32, Pcg32Basic, Pcg32Fast, Pcg32Oneseq, PcgError, SetseqXshRr6432};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn restore_mixin_mismatch() {
+    let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed(42));
+    let err = SetseqXshRr6432::restore_state(ra.get_state()).err();
+    assert_eq!(
+        err,
+        Some(PcgError::MixinMismatch {
+            expected: "XshRr",
+            found: "DXsM".into()
+        })
+    );
+}
+
+#[test]
+fn restore_multiplier_mismatch() {
+    let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed(42));
+    let mut state = ra.get_state();
+    state.multiplier += 4;
+    assert_eq!(
+        Pcg32::restore_state(state).err(),
+        Some(PcgError::MultiplierMismatch)
+    );
+}
+
+#[test]
+fn restore_width_mismatch() {
+    let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed(42));
+    let mut state = ra.get_state();
+    state.output_width = 64;
+    assert_eq!(
+        Pcg32::restore_state(state).err(),
+        Some(PcgError::WidthMismatch {
+            expected: (64, 32),
+            found: (64, 64)
+        })
+    );
+}
+
+#[test]
+fn restore_even_increment() {
+    let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed(42));
+    let mut state = ra.get_state();
+    state.increment = 1234;
+    assert_eq!(
+        Pcg32::restore_state(state).err(),
+        Some(PcgError::EvenIncrement)
+    );
+}
+
+#[test]
+fn restore_stream_mismatch() {
+    let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed(42));
+    assert_eq!(
+        Pcg32Oneseq::restore_state(ra.get_state()).err(),
+        Some(PcgError::StreamMismatch {
+            expected: "OneSeq",
+            found: "SetSeq".into()
+        })
+    );
+
+    //States saved before the stream was recorded are still accepted
+    let mut state = ra.get_state();
+    state.stream = String::new();
+    assert!(Pcg32::restore_state(state).is_ok());
+}
+
+#[test]
+fn restore_fixed_streams() {
+    let mut ra: Pcg32Oneseq = Pcg32Oneseq::from_seed(PcgSeeder::seed(42));
+    let mut rb = Pcg32Oneseq::restore_state(ra.get_state()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut state = ra.get_state();
+    state.increment += 2;
+    assert_eq!(
+        Pcg32Oneseq::restore_state(state).err(),
+        Some(PcgError::IncrementMismatch)
+    );
+
+    let mut ra: Pcg32Fast = Pcg32Fast::from_seed(PcgSeeder::seed(42));
+    let mut rb = Pcg32Fast::restore_state(ra.get_state()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut state = ra.get_state();
+    state.increment = 1;
+    assert_eq!(
+        Pcg32Fast::restore_state(state).err(),
+        Some(PcgError::IncrementMismatch)
+    );
+}
+
+#[test]
+fn restore_basic() {
+    let mut ra = Pcg32Basic::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    let mut rb = Pcg32Basic::restore_state(ra.get_state()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    //Pcg32Basic is the same generator as SetseqXshRr6432
+    let mut rc = SetseqXshRr6432::restore_state(ra.get_state()).unwrap();
+    assert_eq!(ra.next_u32(), rc.next_u32());
+
+    let rd: Pcg32 = Pcg32::from_seed(PcgSeeder::seed(42));
+    assert_eq!(
+        Pcg32Basic::restore_state(rd.get_state()).err(),
+        Some(PcgError::MixinMismatch {
+            expected: "XshRr",
+            found: "DXsM".into()
+        })
+    );
+
+    //The low bit is always set, so an even increment can not be restored
+    for &inc in &[0, 54] {
+        let mut state = ra.get_state();
+        state.increment = inc;
+        assert_eq!(
+            Pcg32Basic::restore_state(state).err(),
+            Some(PcgError::EvenIncrement)
+        );
+    }
+}
+
+#[test]
+fn seed_too_short() {
+    let mut seed = PcgSeeder::seed_with_stream(1u64, 2u64);
+    assert_eq!(seed.get(), Ok(1));
+    assert_eq!(seed.get(), Ok(2));
+    assert_eq!(
+        seed.get(),
+        Err(PcgError::SeedTooShort {
+            needed: 8,
+            available: 0
+        })
+    );
+}
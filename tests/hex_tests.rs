@@ -0,0 +1,111 @@
+#![cfg(feature = "serde1")]
+
+extern crate pcg_rand;
+extern crate rand;
+extern crate serde;
+extern crate serde_json;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32, Pcg64, Pcg64Oneseq};
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Saved<R: pcg_rand::serialization::hex::HexState> {
+    #[serde(with = "pcg_rand::serialization::hex")]
+    rng: R,
+}
+
+#[test]
+fn hex_format() {
+    let saved = Saved {
+        rng: Pcg64::from_seed(PcgSeeder::seed_with_stream(0x2a, 3)),
+    };
+    let value: serde_json::Value = serde_json::to_value(&saved).unwrap();
+    assert_eq!(value["rng"]["state"], "0x0000000000000000000000000000002a");
+    assert_eq!(
+        value["rng"]["increment"],
+        "0x00000000000000000000000000000007"
+    );
+    assert_eq!(value["rng"]["stream"], "SetSeq");
+
+    let saved = Saved {
+        rng: Pcg32::from_seed(PcgSeeder::seed_with_stream(0x2a, 3)),
+    };
+    let value: serde_json::Value = serde_json::to_value(&saved).unwrap();
+    assert_eq!(value["rng"]["state"], "0x000000000000002a");
+}
+
+#[test]
+fn hex_roundtrip() {
+    let mut saved = Saved {
+        rng: Pcg64::from_entropy(),
+    };
+    let text = serde_json::to_string(&saved).unwrap();
+    let mut loaded: Saved<Pcg64> = serde_json::from_str(&text).unwrap();
+    assert_eq!(saved.rng.next_u64(), loaded.rng.next_u64());
+
+    let mut saved = Saved {
+        rng: Pcg64Oneseq::from_entropy(),
+    };
+    let text = serde_json::to_string(&saved).unwrap();
+    let mut loaded: Saved<Pcg64Oneseq> = serde_json::from_str(&text).unwrap();
+    assert_eq!(saved.rng.next_u64(), loaded.rng.next_u64());
+}
+
+#[test]
+fn hex_accepts_numbers() {
+    //A plain checkpoint whose numbers fit in 64 bits
+    let mut ra = Pcg32::from_entropy();
+    let text = format!("{{\"rng\":{}}}", serde_json::to_string(&ra).unwrap());
+    let mut loaded: Saved<Pcg32> = serde_json::from_str(&text).unwrap();
+    assert_eq!(ra.next_u32(), loaded.rng.next_u32());
+
+    //Wide numbers would be rounded, so they are rejected
+    let ra = Pcg64::from_seed(PcgSeeder::seed_with_stream(u128::MAX - 1, 3));
+    let text = format!("{{\"rng\":{}}}", serde_json::to_string(&ra).unwrap());
+    assert!(serde_json::from_str::<Saved<Pcg64>>(&text).is_err());
+}
+
+#[test]
+fn hex_old_wide_checkpoint() {
+    //Written by the plain serde support before the stream was recorded
+    let text = r#"{"rng":{"state":338770000845734292516042252062085074415,
+        "increment":302514906183817587837751960471663069839,
+        "multiplier":47026247687942121848144207491837523525,
+        "internal_width":128,"output_width":64,"output_mixin":"DXsM"}}"#;
+    let err = serde_json::from_str::<Saved<Pcg64>>(text).err().unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("integer does not fit in 64 bits, store it as a hex string"));
+
+    //The plain Deserialize reads it exactly, then it can be saved as hex
+    #[derive(Deserialize)]
+    struct Plain {
+        rng: Pcg64,
+    }
+    let plain: Plain = serde_json::from_str(text).unwrap();
+    assert_eq!(
+        plain.rng.get_state().state,
+        0xfedc_ba98_7654_3210_0123_4567_89ab_cdef
+    );
+
+    let mut saved = Saved { rng: plain.rng };
+    let text = serde_json::to_string(&saved).unwrap();
+    let mut loaded: Saved<Pcg64> = serde_json::from_str(&text).unwrap();
+    assert_eq!(saved.rng.next_u64(), loaded.rng.next_u64());
+}
+
+#[test]
+fn hex_rejects_wrong_kind() {
+    let saved = Saved {
+        rng: Pcg64::from_entropy(),
+    };
+    let text = serde_json::to_string(&saved).unwrap();
+    //The kind is only checked when deserialization verifies
+    #[cfg(not(feature = "no_deserialize_verify"))]
+    assert!(serde_json::from_str::<Saved<Pcg64Oneseq>>(&text).is_err());
+
+    let bad = text.replace("\"0x", "\"0xg");
+    assert!(serde_json::from_str::<Saved<Pcg64>>(&bad).is_err());
+}
@@ -0,0 +1,62 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::{PcgSeeder, StableHasher};
+use pcg_rand::Pcg32;
+use rand::RngCore;
+use std::hash::Hasher;
+
+#[test]
+fn stable_hasher_vectors() {
+    //Reference values of 128 bit FNV-1a
+    let digest = |key: &[u8]| {
+        let mut hasher = StableHasher::new();
+        hasher.write(key);
+        hasher.digest()
+    };
+    assert_eq!(digest(b""), 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d);
+    assert_eq!(digest(b"a"), 0xd228_cb69_6f1a_8caf_7891_2b70_4e4a_8964);
+    assert_eq!(digest(b"foobar"), 0x343e_1662_793c_64bf_6f0d_3597_ba44_6f18);
+}
+
+#[test]
+fn key_seeds_are_pinned() {
+    //These values must never change, users rely on them to reproduce runs
+    assert_eq!(
+        PcgSeeder::<u64>::from_key(b"level-3/boss").parts(),
+        (9_232_359_491_658_638_555, 3_492_238_833_310_370_047)
+    );
+    assert_eq!(
+        PcgSeeder::<u128>::from_key(b"level-3/boss").parts(),
+        (
+            64_420_436_002_346_427_350_799_668_619_065_484_507,
+            195_378_391_464_972_589_717_779_149_517_750_563_184
+        )
+    );
+    assert_eq!(
+        PcgSeeder::<u64>::from_hashable(&(7u32, "boss")).parts(),
+        (15_649_285_317_705_676_223, 8_120_951_120_953_062_582)
+    );
+    assert_eq!(Pcg32::from_key(b"level-3/boss").next_u32(), 4_097_543_806);
+}
+
+#[cfg(feature = "u128")]
+#[test]
+fn pcg64_from_key() {
+    assert_eq!(
+        pcg_rand::Pcg64::from_key(b"level-3/boss").next_u64(),
+        9_345_051_637_511_021_290
+    );
+}
+
+#[test]
+fn key_diff() {
+    let mut ra = Pcg32::from_key(b"level-3/boss");
+    let mut rb = Pcg32::from_key(b"level-3/bosr");
+    let mut rc = Pcg32::from_hashable("level-3/boss");
+    let a: Vec<u32> = (0..10).map(|_| ra.next_u32()).collect();
+    let b: Vec<u32> = (0..10).map(|_| rb.next_u32()).collect();
+    let c: Vec<u32> = (0..10).map(|_| rc.next_u32()).collect();
+    assert_ne!(a, b);
+    assert_ne!(a, c);
+}
@@ -0,0 +1,35 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::{Pcg32, Pcg32Fast, Pcg64};
+use rand::RngCore;
+
+#[test]
+fn pcg32_leapfrog_interleave() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut workers: Vec<_> = (0..4).map(|k| ra.leapfrog(k, 4)).collect();
+
+    for i in 0..1000 {
+        assert_eq!(workers[i % 4].next_u32(), ra.next_u32());
+    }
+}
+
+#[test]
+fn pcg32fast_leapfrog_interleave() {
+    let mut ra: Pcg32Fast = Pcg32Fast::new_unseeded();
+    let mut workers: Vec<_> = (0..3).map(|k| ra.leapfrog(k, 3)).collect();
+
+    for i in 0..1000 {
+        assert_eq!(workers[i % 3].next_u32(), ra.next_u32());
+    }
+}
+
+#[test]
+fn pcg64_leapfrog_interleave() {
+    let mut ra: Pcg64 = Pcg64::new_unseeded();
+    let mut workers: Vec<_> = (0..7).map(|k| ra.leapfrog(k, 7)).collect();
+
+    for i in 0..1000 {
+        assert_eq!(workers[i % 7].next_u64(), ra.next_u64());
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "std")]
+
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::{Pcg32, Pcg32Unique, Pcg64};
+use rand::{Rng, RngCore};
+
+#[test]
+fn pcg32_fill_bytes_parallel() {
+    for &len in &[0, 1, 4, 7, 8, 9, 1000, 4099] {
+        for &threads in &[1, 2, 3, 8] {
+            let mut ra: Pcg32 = Pcg32::new_unseeded();
+            let mut rb: Pcg32 = Pcg32::new_unseeded();
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+
+            ra.fill_bytes(&mut a);
+            rb.fill_bytes_parallel(&mut b, threads);
+
+            assert_eq!(a, b);
+            assert_eq!(ra.next_u32(), rb.next_u32());
+        }
+    }
+}
+
+#[test]
+fn pcg32unique_fill_bytes_parallel() {
+    for &len in &[0, 1, 9, 4099] {
+        for &threads in &[1, 3, 8] {
+            //The copy is made where ra lives, so it is on the same stream
+            let mut ra: Pcg32Unique = Pcg32Unique::new_unseeded();
+            let mut rb = ra.to_setseq();
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+
+            ra.fill_bytes_parallel(&mut a, threads);
+            rb.fill_bytes(&mut b);
+
+            assert_eq!(a, b);
+            assert_eq!(ra.next_u32(), rb.next_u32());
+        }
+    }
+}
+
+#[test]
+fn pcg64_fill_bytes_parallel() {
+    for &len in &[0, 1, 4, 7, 8, 9, 1000, 4099] {
+        for &threads in &[1, 2, 3, 8] {
+            let mut ra: Pcg64 = Pcg64::new_unseeded();
+            let mut rb: Pcg64 = Pcg64::new_unseeded();
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+
+            ra.fill_bytes(&mut a);
+            rb.fill_bytes_parallel(&mut b, threads);
+
+            assert_eq!(a, b);
+            assert_eq!(ra.next_u64(), rb.next_u64());
+        }
+    }
+}
+
+#[test]
+fn pcg32_fill_u32_parallel() {
+    for &len in &[0, 1, 2, 3, 1001] {
+        for &threads in &[1, 2, 5] {
+            let mut ra: Pcg32 = Pcg32::new_unseeded();
+            let mut rb: Pcg32 = Pcg32::new_unseeded();
+            let mut a = vec![0u32; len];
+            let mut b = vec![0u32; len];
+
+            ra.fill(&mut a[..]);
+            rb.fill_u32_parallel(&mut b, threads);
+
+            assert_eq!(a, b);
+            assert_eq!(ra.next_u32(), rb.next_u32());
+        }
+    }
+}
+
+#[test]
+fn pcg64_fill_u32_parallel() {
+    for &len in &[0, 1, 2, 3, 1001] {
+        for &threads in &[1, 2, 5] {
+            let mut ra: Pcg64 = Pcg64::new_unseeded();
+            let mut rb: Pcg64 = Pcg64::new_unseeded();
+            let mut a = vec![0u32; len];
+            let mut b = vec![0u32; len];
+
+            ra.fill(&mut a[..]);
+            rb.fill_u32_parallel(&mut b, threads);
+
+            assert_eq!(a, b);
+            assert_eq!(ra.next_u64(), rb.next_u64());
+        }
+    }
+}
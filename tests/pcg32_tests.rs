@@ -1,9 +1,15 @@
 extern crate pcg_rand;
 extern crate rand;
 
+use pcg_rand::extension::{Ext2, Ext32, Pcg32Ext};
+use pcg_rand::multiplier::DefaultMultiplier;
+use pcg_rand::outputmix::DXsMMixin;
 use pcg_rand::seeds::PcgSeeder;
-use pcg_rand::Pcg32;
-use rand::{distributions::Alphanumeric, thread_rng, Rng, SeedableRng};
+use pcg_rand::stream::LegacySeqStream;
+use pcg_rand::{Pcg32, PcgEngine};
+use rand::{distributions::Alphanumeric, thread_rng, Rng, RngCore, SeedableRng};
+
+type LegacyPcg32 = PcgEngine<u64, u32, LegacySeqStream<u64>, DefaultMultiplier, DXsMMixin>;
 
 const NUM_TESTS: usize = 1000;
 
@@ -49,6 +55,104 @@ fn pcg32_seq_diff() {
     }
 }
 
+#[test]
+fn pcg32_adjacent_seq_diff() {
+    for _ in 0..NUM_TESTS {
+        //Every stream index gives its own sequence, including the
+        //neighbouring even and odd indices
+        let seed: u64 = thread_rng().gen();
+        let seq: u64 = thread_rng().gen::<u64>() & !1;
+        let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq));
+        let rb: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq + 1));
+        assert!(
+            ra.sample_iter(&Alphanumeric).take(100).collect::<Vec<_>>()
+                != rb.sample_iter(&Alphanumeric).take(100).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_get_stream() {
+    let seq: u64 = thread_rng().gen::<u64>() >> 1;
+    let ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, seq));
+    assert_eq!(ra.provenance().stream, seq);
+
+    //Restoring keeps the stream intact
+    let mut rb = Pcg32::restore_state(ra.get_state()).unwrap();
+    assert_eq!(rb.provenance().stream, seq);
+    let mut ra = ra;
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn pcg32_set_stream() {
+    let seed: u64 = thread_rng().gen();
+    let seq: u64 = thread_rng().gen::<u64>() >> 1;
+    let mut ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq));
+    let mut rb: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq ^ 1));
+    rb.set_stream(seq);
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+
+    //The extended generator passes the stream through to its PCG
+    let mut ea = Pcg32Ext::<Ext2>::from_seed(PcgSeeder::seed_with_stream(seed, seq));
+    let mut eb = Pcg32Ext::<Ext2>::from_seed(PcgSeeder::seed_with_stream(seed, seq));
+    eb.set_stream(seq ^ 1);
+    assert!((0..100)
+        .map(|_| ea.next_u32())
+        .ne((0..100).map(|_| eb.next_u32())));
+}
+
+#[test]
+fn pcg32_inline_storage() {
+    //The seed only exposes the bytes of the seed and stream
+    let mut seed = PcgSeeder::<u64>::seed(42);
+    assert_eq!(seed.as_mut().len(), 16);
+
+    //The extension array lives inside the generator
+    assert!(std::mem::size_of::<Pcg32Ext<Ext32>>() >= 32 * std::mem::size_of::<u32>());
+}
+
+#[test]
+fn pcg32_legacy_seq() {
+    for _ in 0..NUM_TESTS {
+        //The legacy stream uses the index as the increment, so it matches
+        //the new stream at half the index
+        let seed: u64 = thread_rng().gen();
+        let seq: u64 = thread_rng().gen();
+        let mut ra = LegacyPcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq));
+        let mut rb = LegacyPcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq ^ 1));
+        let mut rc: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(seed, seq >> 1));
+        for _ in 0..100 {
+            let out = ra.next_u32();
+            assert_eq!(out, rb.next_u32());
+            assert_eq!(out, rc.next_u32());
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_legacy_restore() {
+    let mut ra = LegacyPcg32::from_seed(PcgSeeder::seed_with_stream(42, 7));
+    let mut rb = LegacyPcg32::restore_state(ra.get_state()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    //Both have the same increment, only the recorded stream tells them apart
+    let rc: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 3));
+    assert_eq!(ra.get_state().increment, rc.get_state().increment);
+    assert_eq!(
+        LegacyPcg32::restore_state(rc.get_state()).err(),
+        Some(pcg_rand::PcgError::StreamMismatch {
+            expected: "LegacySetSeq",
+            found: "SetSeq".into()
+        })
+    );
+    assert!(Pcg32::restore_state(ra.get_state()).is_err());
+}
+
 #[test]
 fn pcg32_seed_diff() {
     for _ in 0..NUM_TESTS {
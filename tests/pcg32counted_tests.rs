@@ -0,0 +1,110 @@
+extern crate pcg_rand;
+extern crate rand;
+#[cfg(feature = "serde1")]
+extern crate serde_json;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32Counted, Provenance};
+use rand::{distributions::Alphanumeric, thread_rng, Rng, RngCore, SeedableRng};
+
+const NUM_TESTS: usize = 1000;
+
+fn make_boxed(seed: u64) -> Box<Pcg32Counted> {
+    Box::new(Pcg32Counted::from_seed(PcgSeeder::seed(seed)))
+}
+
+#[test]
+fn pcg32_counted_seed_diff() {
+    for _ in 0..NUM_TESTS {
+        //Even with the same seed every instance has its own stream
+        let seed: u64 = thread_rng().gen();
+        let ra: Pcg32Counted = SeedableRng::from_seed(PcgSeeder::seed(seed));
+        let rb: Pcg32Counted = SeedableRng::from_seed(PcgSeeder::seed(seed));
+        assert!(
+            ra.sample_iter(&Alphanumeric).take(100).collect::<Vec<_>>()
+                != rb.sample_iter(&Alphanumeric).take(100).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn pcg32_counted_move() {
+    let ra = make_boxed(42);
+    let rb = (*ra).clone();
+
+    //Moving the generators around does not change the stream
+    let mut gens = [*ra];
+    let ra = &mut gens[0];
+    let mut rb = Box::new(rb);
+    assert_eq!(ra.provenance().stream, rb.provenance().stream);
+
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_counted_restore() {
+    let mut ra: Pcg32Counted = Pcg32Counted::from_seed(PcgSeeder::seed(42));
+    ra.advance(1000);
+
+    let mut rb = Pcg32Counted::restore_state(ra.get_state()).unwrap();
+    assert_eq!(ra.provenance().stream, rb.provenance().stream);
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+
+    //A new generator does not reuse a restored stream
+    let rc: Pcg32Counted = Pcg32Counted::from_seed(PcgSeeder::seed(42));
+    assert!(rc.provenance().stream > rb.provenance().stream);
+}
+
+#[test]
+fn pcg32_counted_restore_ahead() {
+    //Restore a stream the counter has not reached yet
+    let ra: Pcg32Counted = Pcg32Counted::from_seed(PcgSeeder::seed(42));
+    let ahead = ra.provenance().stream + 1_000_000;
+    let rb = Pcg32Counted::from_provenance(&Provenance {
+        seed: 42,
+        stream: ahead,
+        offset: 0,
+    });
+    assert_eq!(rb.provenance().stream, ahead);
+
+    let rc: Pcg32Counted = Pcg32Counted::from_seed(PcgSeeder::seed(42));
+    assert!(rc.provenance().stream > ahead);
+}
+
+#[test]
+fn pcg32_counted_provenance() {
+    let mut ra: Pcg32Counted = Pcg32Counted::from_seed(PcgSeeder::seed(42));
+    ra.advance(1000);
+
+    let mut rb = Pcg32Counted::from_provenance(&ra.provenance());
+    assert_eq!(ra.provenance(), rb.provenance());
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn pcg32_counted_serde() {
+    let mut ra: Pcg32Counted = Pcg32Counted::from_seed(PcgSeeder::seed(thread_rng().gen()));
+    ra.advance(1000);
+
+    let text = serde_json::to_string(&ra).unwrap();
+    let mut rb: Pcg32Counted = serde_json::from_str(&text).unwrap();
+    for _ in 0..100 {
+        assert_eq!(ra.next_u32(), rb.next_u32());
+    }
+}
+
+#[cfg(all(feature = "serde1", not(feature = "no_deserialize_verify")))]
+#[test]
+fn pcg32_counted_serde_wrong_stream() {
+    let ra: pcg_rand::Pcg32 = pcg_rand::Pcg32::from_seed(PcgSeeder::seed(thread_rng().gen()));
+    let text = serde_json::to_string(&ra).unwrap();
+    assert!(serde_json::from_str::<Pcg32Counted>(&text).is_err());
+}
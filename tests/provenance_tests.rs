@@ -0,0 +1,77 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32, Pcg32Fast, Pcg64, Provenance};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn pcg32_position() {
+    let mut ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 7));
+    assert_eq!(ra.position(), 0);
+
+    for _ in 0..1000 {
+        ra.next_u32();
+    }
+    assert_eq!(ra.position(), 1000);
+
+    ra.advance(1_002_211);
+    ra.step_back();
+    assert_eq!(ra.position(), 1_003_210);
+}
+
+#[test]
+fn pcg32fast_position() {
+    let mut ra: Pcg32Fast = Pcg32Fast::from_seed(PcgSeeder::seed(43));
+    ra.advance(123_456);
+    assert_eq!(ra.position(), 123_456);
+}
+
+#[test]
+fn pcg32_provenance() {
+    let mut ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 7));
+    ra.advance(1_003_211);
+
+    let provenance = ra.provenance();
+    assert_eq!(
+        provenance,
+        Provenance {
+            seed: 42,
+            stream: 7,
+            offset: 1_003_211
+        }
+    );
+    assert_eq!(provenance.to_string(), "seed 42, stream 7, draw 1003211");
+
+    let mut rb = Pcg32::from_provenance(&provenance);
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn pcg64_provenance() {
+    let mut ra: Pcg64 = Pcg64::from_entropy();
+    for _ in 0..100 {
+        ra.next_u64();
+    }
+
+    let mut rb = Pcg64::from_provenance(&ra.provenance());
+    assert_eq!(rb.position(), 100);
+    assert_eq!(ra.next_u64(), rb.next_u64());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_restore_resets_position() {
+    let mut ra: Pcg32 = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 7));
+    ra.advance(1000);
+
+    //The recorded state does not know where the generator was seeded
+    let mut rb = Pcg32::restore_state(ra.get_state()).unwrap();
+    assert_eq!(rb.position(), 0);
+    assert_eq!(rb.provenance().seed, ra.get_state().state);
+
+    let mut rc = Pcg32::from_provenance(&rb.provenance());
+    let out = ra.next_u32();
+    assert_eq!(out, rb.next_u32());
+    assert_eq!(out, rc.next_u32());
+}
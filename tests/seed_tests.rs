@@ -0,0 +1,123 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::{PcgSeeder, Scramble};
+use pcg_rand::Pcg32;
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn seed_layout_u64() {
+    let seeder = PcgSeeder::<u64>::seed_with_stream(0x0102_0304_0506_0708, 0x1112_1314_1516_1718);
+    assert_eq!(
+        seeder.to_bytes(),
+        [
+            0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x18, 0x17, 0x16, 0x15, 0x14, 0x13,
+            0x12, 0x11
+        ]
+    );
+
+    let back = PcgSeeder::<u64>::from_bytes(seeder.to_bytes());
+    assert_eq!(back.parts(), (0x0102_0304_0506_0708, 0x1112_1314_1516_1718));
+}
+
+#[test]
+fn seed_layout_u128() {
+    //The low half of a u128 comes first like every other little endian value
+    let seeder = PcgSeeder::<u128>::seed_with_stream(1 | (2 << 64), 3 | (4 << 64));
+    let bytes = seeder.to_bytes();
+    let mut expected = [0u8; 32];
+    expected[0] = 1;
+    expected[8] = 2;
+    expected[16] = 3;
+    expected[24] = 4;
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn seed_legacy_u128() {
+    //Old versions stored the high half of a u128 first
+    let mut old = [0u8; 32];
+    old[0] = 2;
+    old[8] = 1;
+    old[16] = 4;
+    old[24] = 3;
+
+    let seeder = PcgSeeder::<u128>::from_legacy_bytes(old);
+    assert_eq!(seeder.parts(), (1 | (2 << 64), 3 | (4 << 64)));
+}
+
+#[test]
+fn seed_legacy_u64() {
+    //Only the u128 layout changed
+    let seeder = PcgSeeder::<u64>::seed_with_stream(42, 54);
+    let legacy = PcgSeeder::<u64>::from_legacy_bytes(seeder.to_bytes());
+    assert_eq!(legacy.parts(), (42, 54));
+}
+
+#[test]
+fn seed_raw_bytes() {
+    //Filling the seed through `AsMut` is the same as building it from bytes
+    let mut seeder = PcgSeeder::<u64>::default();
+    seeder.as_mut().copy_from_slice(&[7; 16]);
+    let mut ra = Pcg32::from_seed(seeder);
+    let mut rb = Pcg32::from_seed(PcgSeeder::from_bytes([7; 16]));
+    let mut rc = Pcg32::from_seed(PcgSeeder::seed_with_stream(
+        0x0707_0707_0707_0707,
+        0x0707_0707_0707_0707,
+    ));
+    for _ in 0..100 {
+        let out = ra.next_u32();
+        assert_eq!(out, rb.next_u32());
+        assert_eq!(out, rc.next_u32());
+    }
+}
+
+#[test]
+fn seed_no_cursor() {
+    //Reading from the seed does not change the generator built from it
+    let mut seeder = PcgSeeder::<u64>::seed_with_stream(42, 54);
+    assert_eq!(seeder.get(), Ok(42));
+    let mut ra = Pcg32::from_seed(seeder);
+    let mut rb = Pcg32::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+fn scramble_is_bijective() {
+    let mut seen = [false; 256];
+    for i in 0..=255u8 {
+        seen[i.scramble() as usize] = true;
+    }
+    assert!(seen.iter().all(|&s| s));
+
+    let mut seen = vec![false; 1 << 16];
+    for i in 0..=u16::MAX {
+        seen[i.scramble() as usize] = true;
+    }
+    assert!(seen.iter().all(|&s| s));
+}
+
+#[test]
+fn scrambled_seeds() {
+    let seeder = PcgSeeder::<u64>::seed_with_stream(1, 2);
+    assert_eq!(
+        seeder.scrambled().parts(),
+        (1u64.scramble(), 2u64.scramble())
+    );
+    assert_eq!(
+        PcgSeeder::<u64>::seed(1).scrambled().parts(),
+        (12_605_985_483_715_718_391, 0)
+    );
+    assert_eq!(
+        Pcg32::from_scrambled_seed(PcgSeeder::seed(1)).next_u32(),
+        3_483_402_526
+    );
+
+    //Adjacent seeds start far apart
+    let states: Vec<u64> = (0..16u64)
+        .map(|i| PcgSeeder::seed(i).scrambled().parts().0)
+        .collect();
+    for pair in states.windows(2) {
+        assert!((pair[0] ^ pair[1]).count_ones() > 8);
+    }
+}
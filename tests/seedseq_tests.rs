@@ -0,0 +1,95 @@
+#![cfg(feature = "std")]
+
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::SeedSequence;
+use pcg_rand::{Pcg32, Pcg64, PcgError};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn seedseq_pinned() {
+    //These values must never change, users rely on them to reproduce runs
+    let mut words = [0u32; 4];
+    SeedSequence::new(12345).generate_state(&mut words);
+    assert_eq!(
+        words,
+        [2_688_385_916, 3_048_105_090, 4_196_366_895, 3_152_189_807]
+    );
+
+    SeedSequence::new(12345).spawn(2).unwrap()[1].generate_state(&mut words);
+    assert_eq!(
+        words,
+        [1_457_248_422, 358_904_087, 711_457_119, 482_272_698]
+    );
+
+    let seq = SeedSequence::new(12345);
+    assert_eq!(Pcg32::from_seed(seq.seeder()).next_u32(), 2_874_794_932);
+    assert_eq!(
+        Pcg64::from_seed(seq.seeder()).next_u64(),
+        4_711_480_603_332_476_447
+    );
+}
+
+#[test]
+fn seedseq_integer_entropy() {
+    //Integers are split into little endian words without leading zeros
+    assert_eq!(SeedSequence::new(0), SeedSequence::from_words(&[0]));
+    assert_eq!(
+        SeedSequence::new(0x1_0000_0002),
+        SeedSequence::from_words(&[2, 1])
+    );
+}
+
+#[test]
+fn seedseq_spawn_keys() {
+    let mut root = SeedSequence::new(7);
+    assert!(root.spawn_key().is_empty());
+
+    let mut children = root.spawn(2).unwrap();
+    children.extend(root.spawn(3).unwrap());
+    let keys: Vec<&[u32]> = children.iter().map(|c| c.spawn_key()).collect();
+    assert_eq!(keys, [[0], [1], [2], [3], [4]]);
+
+    //Spawning is deterministic
+    assert_eq!(children, SeedSequence::new(7).spawn(5).unwrap());
+
+    let grandchild = children[4].spawn(1).unwrap().remove(0);
+    assert_eq!(grandchild.spawn_key(), &[4, 0]);
+}
+
+#[test]
+fn seedseq_children_differ() {
+    let mut root = SeedSequence::new(1);
+    let mut seqs = root.spawn(100).unwrap();
+    seqs.push(root);
+    seqs.push(SeedSequence::new(2));
+
+    let mut firsts: Vec<u64> = seqs
+        .iter()
+        .map(|seq| Pcg64::from_seed(seq.seeder()).next_u64())
+        .collect();
+    firsts.sort();
+    firsts.dedup();
+    assert_eq!(firsts.len(), 102);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn seedseq_spawn_limit() {
+    let mut root = SeedSequence::new(7);
+    root.spawn(2).unwrap();
+
+    let too_many = u32::MAX as usize;
+    assert_eq!(
+        root.spawn(too_many),
+        Err(PcgError::SpawnLimit {
+            spawned: 2,
+            requested: too_many
+        })
+    );
+    assert!(root.spawn(too_many + 3).is_err());
+
+    //A failed spawn does not use up any keys
+    assert_eq!(root.spawn(1).unwrap()[0].spawn_key(), &[2]);
+}
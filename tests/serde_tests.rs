@@ -0,0 +1,40 @@
+#![cfg(feature = "serde1")]
+
+extern crate pcg_rand;
+extern crate rand;
+extern crate serde_json;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32Basic, Pcg32Fast, Pcg32Oneseq, Pcg64Oneseq};
+use rand::{RngCore, SeedableRng};
+
+#[test]
+fn serde_fixed_streams() {
+    let mut ra = Pcg32Oneseq::from_seed(PcgSeeder::seed(42));
+    let mut rb: Pcg32Oneseq = serde_json::from_str(&serde_json::to_string(&ra).unwrap()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut ra = Pcg32Fast::from_seed(PcgSeeder::seed(42));
+    let mut rb: Pcg32Fast = serde_json::from_str(&serde_json::to_string(&ra).unwrap()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut ra = Pcg64Oneseq::from_seed(PcgSeeder::seed(42));
+    let mut rb: Pcg64Oneseq = serde_json::from_str(&serde_json::to_string(&ra).unwrap()).unwrap();
+    assert_eq!(ra.next_u64(), rb.next_u64());
+}
+
+#[test]
+fn serde_basic() {
+    let mut ra = Pcg32Basic::from_seed(PcgSeeder::seed_with_stream(42, 54));
+    let mut rb: Pcg32Basic = serde_json::from_str(&serde_json::to_string(&ra).unwrap()).unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+#[cfg(not(feature = "no_deserialize_verify"))]
+fn serde_wrong_kind() {
+    let ra = pcg_rand::Pcg32::from_seed(PcgSeeder::seed(42));
+    let text = serde_json::to_string(&ra).unwrap();
+    assert!(serde_json::from_str::<Pcg32Oneseq>(&text).is_err());
+    assert!(serde_json::from_str::<Pcg32Fast>(&text).is_err());
+}
@@ -0,0 +1,76 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::Pcg64;
+#[cfg(feature = "std")]
+use pcg_rand::{Pcg32, Pcg32Fast};
+use rand::RngCore;
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_split_into() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut split = ra.split_into(4);
+
+    assert_eq!(split.len(), 4);
+    for pair in split.windows(2) {
+        assert_eq!(pair[0].distance(&pair[1]), 1 << 62);
+    }
+    assert_eq!(split[3].distance(&split[0]), 1 << 62);
+    assert_eq!(split[0].next_u32(), ra.next_u32());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_split_into_one() {
+    let mut ra: Pcg32 = Pcg32::new_unseeded();
+    let mut split = ra.split_into(1);
+
+    assert_eq!(split.len(), 1);
+    assert_eq!(split[0].next_u32(), ra.next_u32());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg64_split_into_power_of_two() {
+    let ra: Pcg64 = Pcg64::new_unseeded();
+    let split = ra.split_into(256);
+
+    assert_eq!(split.len(), 256);
+    assert_eq!(ra.distance(&split[1]), 1 << 120);
+    assert_eq!(split[255].distance(&split[0]), 1 << 120);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32_split_into_uneven() {
+    let ra: Pcg32 = Pcg32::new_unseeded();
+    let split = ra.split_into(3);
+
+    assert_eq!(ra.distance(&split[1]), u64::MAX / 3);
+    assert_eq!(ra.distance(&split[2]), 2 * (u64::MAX / 3));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pcg32fast_split_into() {
+    let ra: Pcg32Fast = Pcg32Fast::new_unseeded();
+    let split = ra.split_into(2);
+
+    // The MCG has a shorter period so the blocks have to be smaller
+    let mut rb = split[0].clone();
+    let mut rc = split[1].clone();
+    assert!(rb.next_u32() != rc.next_u32());
+    assert_eq!(split[1].distance(&split[0]), split[0].distance(&split[1]));
+}
+
+#[test]
+fn pcg64_substreams() {
+    let ra: Pcg64 = Pcg64::new_unseeded();
+
+    for (i, mut sub) in ra.substreams(1_000_000).take(10).enumerate() {
+        let mut rb = ra.clone();
+        rb.advance(i as u128 * 1_000_000);
+        assert_eq!(sub.next_u64(), rb.next_u64());
+    }
+}
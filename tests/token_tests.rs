@@ -0,0 +1,109 @@
+extern crate pcg_rand;
+extern crate rand;
+
+use pcg_rand::seeds::PcgSeeder;
+use pcg_rand::{Pcg32, Pcg32Fast, Pcg32L};
+#[cfg(feature = "std")]
+use pcg_rand::{Pcg32Oneseq, Pcg64, PcgError};
+#[cfg(feature = "std")]
+use rand::RngCore;
+use rand::SeedableRng;
+
+#[test]
+fn token_format() {
+    let ra = Pcg32::from_seed(PcgSeeder::seed_with_stream(0x1234, 7));
+    assert_eq!(ra.to_string(), "pcg32-dxsm-setseq:state=0x1234:inc=0xf");
+
+    let ra = Pcg32Fast::from_seed(PcgSeeder::seed(0xabc));
+    assert_eq!(ra.to_string(), "pcg32-xshrs-noseq:state=0xabc:inc=0x0");
+
+    let ra = Pcg32L::from_seed(PcgSeeder::seed_with_stream(1, 0));
+    assert_eq!(ra.to_string(), "pcg128x32-dxsm-setseq:state=0x1:inc=0x1");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn token_roundtrip() {
+    let mut ra = Pcg32::from_entropy();
+    let mut rb: Pcg32 = ra.to_string().parse().unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut ra = Pcg64::from_entropy();
+    let mut rb: Pcg64 = ra.to_string().parse().unwrap();
+    assert_eq!(ra.next_u64(), rb.next_u64());
+
+    let mut ra = Pcg32Oneseq::from_entropy();
+    let mut rb: Pcg32Oneseq = ra.to_string().parse().unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    let mut ra = Pcg32Fast::from_entropy();
+    let mut rb: Pcg32Fast = ra.to_string().parse().unwrap();
+    assert_eq!(ra.next_u32(), rb.next_u32());
+
+    //Tokens pasted from a log can have other case and whitespace
+    let mut rb: Pcg32 = " PCG32-DXsM-SetSeq:state=0x1234:inc=0xF\n".parse().unwrap();
+    let mut ra = Pcg32::from_seed(PcgSeeder::seed_with_stream(0x1234, 7));
+    assert_eq!(ra.next_u32(), rb.next_u32());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn token_errors() {
+    let parse = |token: &str| token.parse::<Pcg32>().err();
+
+    assert_eq!(
+        parse("pcg32-xshrr-setseq:state=0x1:inc=0x1"),
+        Some(PcgError::MixinMismatch {
+            expected: "DXsM",
+            found: "xshrr".into()
+        })
+    );
+    assert_eq!(
+        parse("pcg32-dxsm-oneseq:state=0x1:inc=0x1"),
+        Some(PcgError::StreamMismatch {
+            expected: "SetSeq",
+            found: "oneseq".into()
+        })
+    );
+    //Unknown ids can belong to mixins and streams outside the crate, so they
+    //are reported as a mismatch and the mixin is checked first
+    assert_eq!(
+        parse("pcg32-dxsm-foo:state=0x1:inc=0x1"),
+        Some(PcgError::StreamMismatch {
+            expected: "SetSeq",
+            found: "foo".into()
+        })
+    );
+    assert_eq!(
+        parse("pcg32-foo-foo:state=0x1:inc=0x1"),
+        Some(PcgError::MixinMismatch {
+            expected: "DXsM",
+            found: "foo".into()
+        })
+    );
+    assert_eq!(
+        parse("pcg64-dxsm-setseq:state=0x1:inc=0x1"),
+        Some(PcgError::WidthMismatch {
+            expected: (64, 32),
+            found: (128, 64)
+        })
+    );
+    assert_eq!(
+        parse("pcg32-dxsm-setseq:state=0x1:inc=0x2"),
+        Some(PcgError::EvenIncrement)
+    );
+
+    for bad in &[
+        "",
+        "pcg32",
+        "pcg32-dxsm-setseq",
+        "pcg32-dxsm-setseq:state=0x1",
+        "pcg32-dxsm-setseq:state=1:inc=0x1",
+        "pcg32-dxsm-setseq:state=0xz:inc=0x1",
+        "pcg32-dxsm-setseq:state=0x1:inc=0x1:more",
+        "pcg32-dxsm:state=0x1:inc=0x1",
+        "mt19937-dxsm-setseq:state=0x1:inc=0x1",
+    ] {
+        assert_eq!(parse(bad), Some(PcgError::InvalidToken), "{}", bad);
+    }
+}